@@ -0,0 +1,242 @@
+use miette::{miette, LabeledSpan};
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::{
+    parser::{Commit, CommitSpan},
+    rules::{
+        self, scope_case::ScopeCaseRule, scope_enum::ScopeEnumRule, type_case::TypeCaseRule,
+        type_enum::TypeEnumRule, CaseOpts, EnumOpts, LintContext, LintResult, Rule, TypedEnumOpts,
+        TypedValues,
+    },
+};
+
+/// Regex for branch names with the named group `type` and optionally `scope` and `description`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "String")]
+pub struct BranchPattern(Regex);
+
+impl TryFrom<String> for BranchPattern {
+    type Error = String;
+
+    fn try_from(pattern: String) -> Result<Self, Self::Error> {
+        let regex = Regex::new(&pattern).map_err(|e| e.to_string())?;
+        if !regex.capture_names().flatten().any(|name| name == "type") {
+            return Err(String::from(
+                "the branch pattern needs the named group `type`",
+            ));
+        }
+
+        Ok(BranchPattern(regex))
+    }
+}
+
+/// Options for branch names, configured in the `[branch]` table.
+/// The type and the scope are checked like the ones of a commit header.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct BranchOptions {
+    /// Pattern of the branch names (e.g. `feat/PROJ-123-description`)
+    pub pattern: BranchPattern,
+    /// Branches which are not linted
+    pub ignore: Vec<String>,
+    pub type_enum: EnumOpts,
+    pub type_case: CaseOpts,
+    pub scope_enum: EnumOpts,
+    pub scope_case: CaseOpts,
+}
+
+impl Default for BranchOptions {
+    fn default() -> Self {
+        BranchOptions {
+            pattern: BranchPattern::try_from(String::from(
+                r"^(?P<type>[^/]+)/(?:(?P<scope>[A-Z][A-Z0-9]*-[0-9]+)-)?(?P<description>.+)$",
+            ))
+            .expect("the default pattern is valid"),
+            ignore: ["main", "master", "develop", "HEAD"]
+                .map(String::from)
+                .to_vec(),
+            type_enum: serde_json::from_str(rules::type_enum::META.default)
+                .expect("the default options of all rules are valid"),
+            type_case: serde_json::from_str(r#"["error", "always", "lower-case"]"#)
+                .expect("the options are valid"),
+            scope_enum: serde_json::from_str(r#"["off", "always", []]"#)
+                .expect("the options are valid"),
+            scope_case: serde_json::from_str(r#"["off", "always", "upper-case"]"#)
+                .expect("the options are valid"),
+        }
+    }
+}
+
+/// Lint the name of a branch: it has to match the pattern and its type and scope are checked by the rules
+///
+/// ```
+/// use commitguard::branch::{lint_branch, BranchOptions};
+///
+/// let options = BranchOptions::default();
+/// assert!(!lint_branch("feat/PROJ-123-add-login", &options).has_errors());
+/// assert!(!lint_branch("main", &options).has_errors());
+/// assert!(lint_branch("Feat/add-login", &options).has_errors());
+/// assert!(lint_branch("add-login", &options).has_errors());
+/// ```
+pub fn lint_branch(name: &str, options: &BranchOptions) -> LintResult {
+    if options.ignore.iter().any(|ignored| ignored == name) {
+        return LintResult::default();
+    }
+
+    let Some(captures) = options.pattern.0.captures(name) else {
+        return LintResult::from_parse_error(
+            miette!(
+                labels = vec![LabeledSpan::at(0..name.len(), "doesn't match the pattern")],
+                help = format!("branch name must match `{}`", options.pattern.0),
+                code = "branch",
+                "Branch name could not be parsed",
+            )
+            .with_source_code(name.to_string()),
+        );
+    };
+
+    let span = |group: &str| {
+        captures
+            .name(group)
+            .map(|found| CommitSpan::new(found.as_str(), found.start(), found.end()))
+    };
+    let commit = Commit {
+        header: CommitSpan::new(name, 0, name.len()),
+        commit_type: span("type").expect("the pattern has a type"),
+        scope: span("scope"),
+        scopes: span("scope").into_iter().collect(),
+        subject: span("description").unwrap_or_default(),
+        raw: name.to_string(),
+        ..Commit::new()
+    };
+
+    let rules: Vec<Box<dyn Rule>> = vec![
+        Box::new(TypeEnumRule {
+            opts: options.type_enum.clone(),
+        }),
+        Box::new(TypeCaseRule {
+            opts: options.type_case.clone(),
+        }),
+        Box::new(ScopeEnumRule {
+            opts: TypedEnumOpts(
+                options.scope_enum.0.clone(),
+                options.scope_enum.1.clone(),
+                TypedValues::All(options.scope_enum.2.clone()),
+            ),
+        }),
+        Box::new(ScopeCaseRule {
+            opts: options.scope_case.clone(),
+        }),
+    ];
+
+    rules::lint_rules(
+        &commit,
+        rules.iter().map(Box::as_ref),
+        &LintContext::default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::RulesConfig;
+
+    use super::*;
+
+    fn codes(result: &LintResult) -> Vec<String> {
+        result
+            .errors()
+            .into_iter()
+            .flatten()
+            .map(|report| report.code().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_default_options() {
+        let options = BranchOptions::default();
+
+        assert!(codes(&lint_branch("fix/login-crash", &options)).is_empty());
+        assert!(codes(&lint_branch("feat/PROJ-1-add-login", &options)).is_empty());
+        // every type is allowed like with the default `type-enum` rule
+        assert!(codes(&lint_branch("feature/add-login", &options)).is_empty());
+        assert_eq!(
+            codes(&lint_branch("Feat/add-login", &options)),
+            ["rule/type-case"]
+        );
+        assert_eq!(codes(&lint_branch("add-login", &options)), ["branch"]);
+    }
+
+    #[test]
+    fn test_labels_point_into_the_branch() {
+        let result = lint_branch("Feature/add-login", &BranchOptions::default());
+        let report = &result.errors().unwrap()[0];
+        let label = report.labels().unwrap().next().unwrap();
+
+        assert_eq!((label.offset(), label.len()), (0, 7));
+    }
+
+    #[test]
+    fn test_options_from_config() {
+        let config = RulesConfig::from_toml(
+            r#"
+            [branch]
+            pattern = "^(?P<type>[a-z]+)/(?P<scope>[A-Z]+-[0-9]+)-(?P<description>[a-z0-9-]+)$"
+            ignore = ["main", "release"]
+            type-enum = ["error", "always", ["feat", "fix"]]
+            scope-enum = ["error", "always", ["PROJ-1"]]
+            "#,
+        )
+        .unwrap();
+        let options = config.branch_options();
+
+        assert!(codes(&lint_branch("feat/PROJ-1-add-login", options)).is_empty());
+        assert!(codes(&lint_branch("release", options)).is_empty());
+        assert_eq!(
+            codes(&lint_branch("chore/PROJ-1-update", options)),
+            ["rule/type-enum"]
+        );
+        assert_eq!(
+            codes(&lint_branch("feat/PROJ-2-add-login", options)),
+            ["rule/scope-enum"]
+        );
+        assert_eq!(codes(&lint_branch("feat/add-login", options)), ["branch"]);
+    }
+
+    #[test]
+    fn test_types_of_type_enum() {
+        let config = RulesConfig::from_toml(
+            r#"
+            [rules]
+            type-enum = ["error", "always", ["feat", "hotfix"]]
+            "#,
+        )
+        .unwrap();
+        let options = config.branch_options();
+
+        assert!(codes(&lint_branch("hotfix/login-crash", options)).is_empty());
+        assert_eq!(
+            codes(&lint_branch("fix/login-crash", options)),
+            ["rule/type-enum"]
+        );
+
+        // the `[branch]` table has its own types
+        let config = RulesConfig::from_toml(
+            r#"
+            [rules]
+            type-enum = ["error", "always", ["feat", "hotfix"]]
+
+            [branch]
+            type-enum = ["error", "always", ["fix"]]
+            "#,
+        )
+        .unwrap();
+        assert!(codes(&lint_branch("fix/login-crash", config.branch_options())).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        assert!(BranchPattern::try_from(String::from("^[a-z]+/.+$")).is_err());
+        assert!(BranchPattern::try_from(String::from("^(?P<type>[a-z]+/.+$")).is_err());
+    }
+}
@@ -0,0 +1,263 @@
+use std::{collections::BTreeMap, fmt};
+
+use serde::Deserialize;
+
+use crate::parser::Commit;
+
+/// Part of the version which has to be increased for a release
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Bump {
+    None,
+    Patch,
+    Minor,
+    Major,
+}
+
+impl fmt::Display for Bump {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Bump::None => write!(f, "none"),
+            Bump::Patch => write!(f, "patch"),
+            Bump::Minor => write!(f, "minor"),
+            Bump::Major => write!(f, "major"),
+        }
+    }
+}
+
+/// Options for the version bump, configured in the `[bump]` table
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct BumpOptions {
+    /// Bump of every type, other types don't need a release (`feat` is `minor`, `fix` and `perf` are `patch` by default)
+    pub types: BTreeMap<String, Bump>,
+    /// While the major version is 0, breaking changes only bump the minor version and everything else the patch version
+    pub pre_major: bool,
+}
+
+impl Default for BumpOptions {
+    fn default() -> Self {
+        BumpOptions {
+            types: BTreeMap::from([
+                (String::from("feat"), Bump::Minor),
+                (String::from("fix"), Bump::Patch),
+                (String::from("perf"), Bump::Patch),
+            ]),
+            pre_major: true,
+        }
+    }
+}
+
+/// The highest bump which the commits require, breaking changes always require a major bump
+///
+/// ```
+/// use commitguard::{
+///     bump::{required_bump, Bump, BumpOptions},
+///     parse_commit,
+/// };
+///
+/// let commits = vec![
+///     parse_commit("fix: fix crash").unwrap(),
+///     parse_commit("feat(api): add endpoint").unwrap(),
+/// ];
+/// assert_eq!(required_bump(&commits, &BumpOptions::default()), Bump::Minor);
+/// ```
+pub fn required_bump(commits: &[Commit], options: &BumpOptions) -> Bump {
+    commits
+        .iter()
+        .map(|commit| match commit.breaking_change() {
+            Some(_) => Bump::Major,
+            None => options
+                .types
+                .get(commit.commit_type.as_str())
+                .copied()
+                .unwrap_or(Bump::None),
+        })
+        .max()
+        .unwrap_or(Bump::None)
+}
+
+/// A semantic version without pre-release and build metadata
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl Version {
+    /// Parse a version tag like `1.2.3` or `v1.2.3`, pre-releases (e.g. `1.2.3-rc.1`) are no releases
+    pub fn parse(tag: &str) -> Option<Self> {
+        let mut parts = tag.strip_prefix('v').unwrap_or(tag).split('.');
+        let mut part = || -> Option<u64> {
+            let part = parts.next()?;
+            // leading zeros are not allowed in semantic versions
+            match part.len() > 1 && part.starts_with('0') {
+                true => None,
+                false => part.parse().ok(),
+            }
+        };
+        let version = Version {
+            major: part()?,
+            minor: part()?,
+            patch: part()?,
+        };
+
+        parts.next().is_none().then_some(version)
+    }
+
+    /// The bump which is applied to this version, while the major version is 0 (`pre_major`)
+    /// every bump is one part lower
+    ///
+    /// ```
+    /// use commitguard::bump::{Bump, Version};
+    ///
+    /// let version = Version::parse("0.4.1").unwrap();
+    /// assert_eq!(version.applied_bump(Bump::Major, true), Bump::Minor);
+    /// assert_eq!(version.applied_bump(Bump::Major, false), Bump::Major);
+    /// ```
+    pub fn applied_bump(&self, bump: Bump, pre_major: bool) -> Bump {
+        match (pre_major && self.major == 0, bump) {
+            (true, Bump::Major) => Bump::Minor,
+            (true, Bump::Minor) => Bump::Patch,
+            (_, bump) => bump,
+        }
+    }
+
+    /// The next version after the bump
+    ///
+    /// ```
+    /// use commitguard::bump::{Bump, Version};
+    ///
+    /// let version = Version::parse("v1.2.3").unwrap();
+    /// assert_eq!(version.bump(Bump::Minor, true).to_string(), "1.3.0");
+    ///
+    /// let version = Version::parse("0.4.1").unwrap();
+    /// assert_eq!(version.bump(Bump::Major, true).to_string(), "0.5.0");
+    /// assert_eq!(version.bump(Bump::Major, false).to_string(), "1.0.0");
+    /// ```
+    pub fn bump(&self, bump: Bump, pre_major: bool) -> Version {
+        match self.applied_bump(bump, pre_major) {
+            Bump::None => *self,
+            Bump::Patch => Version {
+                patch: self.patch + 1,
+                ..*self
+            },
+            Bump::Minor => Version {
+                minor: self.minor + 1,
+                patch: 0,
+                ..*self
+            },
+            Bump::Major => Version {
+                major: self.major + 1,
+                minor: 0,
+                patch: 0,
+            },
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_commit;
+
+    use super::*;
+
+    fn bump(messages: &[&str], options: &BumpOptions) -> Bump {
+        let commits: Vec<_> = messages
+            .iter()
+            .map(|message| parse_commit(message).unwrap())
+            .collect();
+        required_bump(&commits, options)
+    }
+
+    #[test]
+    fn test_required_bump() {
+        let options = BumpOptions::default();
+
+        assert_eq!(bump(&[], &options), Bump::None);
+        assert_eq!(
+            bump(&["chore: update deps", "docs: fix typo"], &options),
+            Bump::None
+        );
+        assert_eq!(
+            bump(&["chore: update deps", "fix: fix crash"], &options),
+            Bump::Patch
+        );
+        assert_eq!(
+            bump(&["fix: fix crash", "feat: add feature"], &options),
+            Bump::Minor
+        );
+        assert_eq!(
+            bump(&["feat: add feature", "chore!: drop Node 16"], &options),
+            Bump::Major
+        );
+        assert_eq!(
+            bump(
+                &["fix: fix crash\n\nBREAKING CHANGE: returns an error now"],
+                &options
+            ),
+            Bump::Major
+        );
+    }
+
+    #[test]
+    fn test_types_from_config() {
+        let options: BumpOptions =
+            serde_json::from_str(r#"{"types": {"feat": "minor", "docs": "patch"}}"#).unwrap();
+
+        assert_eq!(bump(&["docs: fix typo"], &options), Bump::Patch);
+        assert_eq!(bump(&["fix: fix crash"], &options), Bump::None);
+        assert!(options.pre_major);
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(
+            Version::parse("v1.2.3"),
+            Some(Version {
+                major: 1,
+                minor: 2,
+                patch: 3
+            })
+        );
+        assert_eq!(Version::parse("10.0.0").unwrap().major, 10);
+        assert_eq!(Version::parse("1.2.3-rc.1"), None);
+        assert_eq!(Version::parse("1.2"), None);
+        assert_eq!(Version::parse("1.2.3.4"), None);
+        assert_eq!(Version::parse("01.2.3"), None);
+        assert_eq!(Version::parse("release"), None);
+    }
+
+    #[test]
+    fn test_bump_version() {
+        let version = Version::parse("1.2.3").unwrap();
+        assert_eq!(version.bump(Bump::None, true).to_string(), "1.2.3");
+        assert_eq!(version.bump(Bump::Patch, true).to_string(), "1.2.4");
+        assert_eq!(version.bump(Bump::Minor, true).to_string(), "1.3.0");
+        assert_eq!(version.bump(Bump::Major, true).to_string(), "2.0.0");
+
+        let version = Version::parse("0.2.3").unwrap();
+        assert_eq!(version.bump(Bump::Minor, true).to_string(), "0.2.4");
+        assert_eq!(version.bump(Bump::Minor, false).to_string(), "0.3.0");
+    }
+
+    #[test]
+    fn test_applied_bump() {
+        let version = Version::parse("0.2.3").unwrap();
+        assert_eq!(version.applied_bump(Bump::Major, true), Bump::Minor);
+        assert_eq!(version.applied_bump(Bump::Minor, true), Bump::Patch);
+        assert_eq!(version.applied_bump(Bump::Patch, true), Bump::Patch);
+        assert_eq!(version.applied_bump(Bump::None, true), Bump::None);
+        assert_eq!(version.applied_bump(Bump::Major, false), Bump::Major);
+
+        let version = Version::parse("1.2.3").unwrap();
+        assert_eq!(version.applied_bump(Bump::Major, true), Bump::Major);
+    }
+}
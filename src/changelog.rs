@@ -0,0 +1,337 @@
+use std::fmt::Write;
+
+use serde::Deserialize;
+
+use crate::parser::Commit;
+
+/// Title of the section with the commits of types without a configured section
+const OTHER_TITLE: &str = "Other";
+
+/// Title of the section which calls out the breaking changes
+const BREAKING_TITLE: &str = "Breaking Changes";
+
+/// Heading of the section with the changes which are not released yet
+const UNRELEASED_HEADING: &str = "## [Unreleased]";
+
+/// Start of a new changelog file
+const CHANGELOG_HEADER: &str =
+    "# Changelog\n\nAll notable changes to this project will be documented in this file.\n";
+
+/// Section of the changelog with the commits of a type, types with the same title share a section
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChangelogSection {
+    #[serde(rename = "type")]
+    pub commit_type: String,
+    pub title: String,
+}
+
+/// Options for the changelog, configured in the `[changelog]` table
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct ChangelogOptions {
+    /// Sections in the order of the changelog (`Added`, `Fixed` and `Changed` by default)
+    pub sections: Vec<ChangelogSection>,
+    /// Types which are left out, unless their commits are breaking changes
+    pub hidden_types: Vec<String>,
+    /// Link of an issue, `{prefix}` and `{issue}` are replaced (e.g. `https://github.com/org/repo/issues/{issue}`)
+    pub issue_url: Option<String>,
+    /// Link of a commit, `{hash}` is replaced by the full hash (e.g. `https://github.com/org/repo/commit/{hash}`)
+    pub commit_url: Option<String>,
+}
+
+impl Default for ChangelogOptions {
+    fn default() -> Self {
+        let section = |commit_type: &str, title: &str| ChangelogSection {
+            commit_type: String::from(commit_type),
+            title: String::from(title),
+        };
+
+        ChangelogOptions {
+            sections: vec![
+                section("feat", "Added"),
+                section("fix", "Fixed"),
+                section("perf", "Changed"),
+                section("refactor", "Changed"),
+                section("revert", "Changed"),
+            ],
+            hidden_types: ["build", "chore", "ci", "docs", "style", "test"]
+                .map(String::from)
+                .to_vec(),
+            issue_url: None,
+            commit_url: None,
+        }
+    }
+}
+
+/// A commit of a release with its hash
+pub struct ChangelogCommit<'a> {
+    pub hash: String,
+    pub commit: Commit<'a>,
+}
+
+/// Markdown line of a commit with its scope, the references and the hash
+fn entry(commit: &ChangelogCommit, text: &str, options: &ChangelogOptions) -> String {
+    let mut line = String::from("- ");
+    if let Some(scope) = &commit.commit.scope {
+        let _ = write!(line, "**{}:** ", scope);
+    }
+    line += text;
+
+    let mut links: Vec<String> = vec![];
+    for reference in &commit.commit.references {
+        let text = format!("{}{}", reference.prefix, reference.issue);
+        let link = match &options.issue_url {
+            Some(url) => format!(
+                "[{}]({})",
+                text,
+                url.replace("{prefix}", reference.prefix.as_str())
+                    .replace("{issue}", reference.issue.as_str())
+            ),
+            None => text,
+        };
+        if !links.contains(&link) {
+            links.push(link);
+        }
+    }
+
+    let short_hash = &commit.hash[..commit.hash.len().min(7)];
+    links.push(match &options.commit_url {
+        Some(url) => format!("[{}]({})", short_hash, url.replace("{hash}", &commit.hash)),
+        None => short_hash.to_string(),
+    });
+
+    format!("{} ({})", line, links.join(", "))
+}
+
+/// Render the Markdown section of a release in the format of keep-a-changelog.
+/// The commits are listed in the given order, commits of hidden types are left out
+/// unless they are breaking changes, which are listed in their own section first.
+///
+/// ```
+/// use commitguard::{
+///     changelog::{render_release, ChangelogCommit, ChangelogOptions},
+///     parse_commit,
+/// };
+///
+/// let commits = vec![ChangelogCommit {
+///     hash: String::from("0123456789abcdef"),
+///     commit: parse_commit("feat(api): add endpoint").unwrap(),
+/// }];
+/// let release = render_release("1.0.0", Some("2024-01-31"), &commits, &ChangelogOptions::default());
+///
+/// assert_eq!(
+///     release,
+///     "## [1.0.0] - 2024-01-31\n\n### Added\n\n- **api:** add endpoint (0123456)\n"
+/// );
+/// ```
+pub fn render_release(
+    version: &str,
+    date: Option<&str>,
+    commits: &[ChangelogCommit],
+    options: &ChangelogOptions,
+) -> String {
+    let mut breaking = vec![];
+    let mut by_title: Vec<(&str, Vec<String>)> = vec![];
+    for section in &options.sections {
+        if !by_title.iter().any(|(title, _)| *title == section.title) {
+            by_title.push((section.title.as_str(), vec![]));
+        }
+    }
+    let mut other = vec![];
+
+    for commit in commits {
+        if let Some(description) = commit.commit.breaking_change() {
+            breaking.push(entry(commit, description, options));
+        }
+
+        let commit_type = commit.commit.commit_type.as_str();
+        if options
+            .hidden_types
+            .iter()
+            .any(|hidden| hidden == commit_type)
+        {
+            continue;
+        }
+
+        let line = entry(commit, commit.commit.subject.as_str(), options);
+        let section = options
+            .sections
+            .iter()
+            .find(|section| section.commit_type == commit_type)
+            .and_then(|section| {
+                by_title
+                    .iter_mut()
+                    .find(|(title, _)| *title == section.title)
+            });
+        match section {
+            Some((_, entries)) => entries.push(line),
+            None => other.push(line),
+        }
+    }
+    let mut sections = vec![(BREAKING_TITLE, breaking)];
+    sections.extend(by_title);
+    sections.push((OTHER_TITLE, other));
+
+    let mut release = format!("## [{}]", version);
+    if let Some(date) = date {
+        let _ = write!(release, " - {}", date);
+    }
+    release.push('\n');
+    for (title, entries) in sections.iter().filter(|(_, entries)| !entries.is_empty()) {
+        let _ = write!(release, "\n### {}\n\n{}\n", title, entries.join("\n"));
+    }
+
+    release
+}
+
+/// Insert the release in front of the latest release of the changelog,
+/// an empty changelog gets the usual header first.
+///
+/// An `## [Unreleased]` section is replaced by the release, because the release contains its commits.
+///
+/// ```
+/// use commitguard::changelog::prepend_release;
+///
+/// let changelog = "# Changelog\n\n## [1.0.0] - 2024-01-31\n\n### Added\n\n- first release\n";
+/// let release = "## [1.1.0] - 2024-02-29\n\n### Fixed\n\n- fix crash\n";
+///
+/// assert_eq!(
+///     prepend_release(changelog, release),
+///     "# Changelog\n\n## [1.1.0] - 2024-02-29\n\n### Fixed\n\n- fix crash\n\n## [1.0.0] - 2024-01-31\n\n### Added\n\n- first release\n"
+/// );
+/// ```
+pub fn prepend_release(changelog: &str, release: &str) -> String {
+    if changelog.trim().is_empty() {
+        return format!("{}\n{}", CHANGELOG_HEADER, release);
+    }
+
+    // start and end of the first release section
+    let mut section: Option<(usize, usize)> = None;
+    let mut offset = 0;
+    for line in changelog.split_inclusive('\n') {
+        if line.starts_with("## ") {
+            match section {
+                Some((start, _)) => {
+                    section = Some((start, offset));
+                    break;
+                }
+                None => section = Some((offset, changelog.len())),
+            }
+        }
+        offset += line.len();
+    }
+
+    match section {
+        Some((start, end)) if changelog[start..].starts_with(UNRELEASED_HEADING) => {
+            let rest = &changelog[end..];
+            match rest.is_empty() {
+                true => format!("{}{}", &changelog[..start], release),
+                false => format!("{}{}\n{}", &changelog[..start], release, rest),
+            }
+        }
+        Some((start, _)) => format!(
+            "{}{}\n{}",
+            &changelog[..start],
+            release,
+            &changelog[start..]
+        ),
+        None => format!("{}\n\n{}", changelog.trim_end(), release),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_commit;
+
+    use super::*;
+
+    fn commits<'a>(messages: &[&'a str]) -> Vec<ChangelogCommit<'a>> {
+        messages
+            .iter()
+            .enumerate()
+            .map(|(i, message)| ChangelogCommit {
+                hash: format!("abc{}", i + 1).repeat(10),
+                commit: parse_commit(message).unwrap(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_render_release() {
+        let commits = commits(&[
+            "feat(api): add endpoint\n\nCloses #12",
+            "fix: fix crash",
+            "chore: update dependencies",
+            "chore!: drop support for Node 16",
+            "perf(db): cache queries",
+            "feat(api)!: remove v1 endpoints\n\nBREAKING CHANGE: the v1 endpoints were removed, use v2 instead",
+            "wip: something",
+        ]);
+        let options = ChangelogOptions {
+            issue_url: Some(String::from("https://github.com/org/repo/issues/{issue}")),
+            commit_url: Some(String::from("https://github.com/org/repo/commit/{hash}")),
+            ..Default::default()
+        };
+
+        insta::assert_snapshot!(render_release(
+            "2.0.0",
+            Some("2024-01-31"),
+            &commits,
+            &options
+        ));
+    }
+
+    #[test]
+    fn test_sections_from_config() {
+        let options: ChangelogOptions = serde_json::from_str(
+            r#"{
+                "sections": [{ "type": "fix", "title": "Bug Fixes" }, { "type": "feat", "title": "Features" }],
+                "hidden-types": ["perf"]
+            }"#,
+        )
+        .unwrap();
+        let commits = commits(&["feat: add feature", "fix: fix crash", "perf: cache"]);
+
+        assert_eq!(
+            render_release("Unreleased", None, &commits, &options),
+            "## [Unreleased]\n\n### Bug Fixes\n\n- fix crash (abc2abc)\n\n### Features\n\n- add feature (abc1abc)\n"
+        );
+    }
+
+    #[test]
+    fn test_prepend_release() {
+        let release = "## [1.0.0]\n\n- first\n";
+
+        assert_eq!(
+            prepend_release("", release),
+            format!("{}\n{}", CHANGELOG_HEADER, release)
+        );
+        assert_eq!(
+            prepend_release("# Changelog\n", release),
+            "# Changelog\n\n## [1.0.0]\n\n- first\n"
+        );
+    }
+
+    #[test]
+    fn test_prepend_unreleased() {
+        let changelog = "# Changelog\n\n## [Unreleased]\n\n- second\n\n## [1.0.0]\n\n- first\n";
+
+        // running it again replaces the section instead of adding another one
+        let unreleased = "## [Unreleased]\n\n- second\n- third\n";
+        assert_eq!(
+            prepend_release(changelog, unreleased),
+            "# Changelog\n\n## [Unreleased]\n\n- second\n- third\n\n## [1.0.0]\n\n- first\n"
+        );
+        assert_eq!(
+            prepend_release("# Changelog\n\n## [Unreleased]\n\n- second\n", unreleased),
+            "# Changelog\n\n## [Unreleased]\n\n- second\n- third\n"
+        );
+
+        // the release contains the commits which were unreleased before
+        let release = "## [1.1.0] - 2024-02-29\n\n- second\n";
+        assert_eq!(
+            prepend_release(changelog, release),
+            "# Changelog\n\n## [1.1.0] - 2024-02-29\n\n- second\n\n## [1.0.0]\n\n- first\n"
+        );
+    }
+}
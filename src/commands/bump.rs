@@ -0,0 +1,97 @@
+use std::{path::Path, process::ExitCode};
+
+use commitguard::{
+    bump::{required_bump, Bump, Version},
+    config, Commit, Linter,
+};
+
+use crate::git;
+
+/// Options for the version bump
+#[derive(clap::Args, Debug)]
+pub struct BumpArgs {
+    /// Only use the commits after this revision, defaults to the latest version tag
+    #[arg(long)]
+    from: Option<String>,
+
+    /// Last revision of the release
+    #[arg(long, default_value = "HEAD")]
+    to: String,
+
+    /// Print the bump (`major`, `minor`, `patch` or `none`) of the version instead of the next version
+    #[arg(long)]
+    bump_only: bool,
+}
+
+pub fn run(args: &BumpArgs, cwd: &Path, config_path: &Path) -> ExitCode {
+    let config = match config::load_config(config::find_config_file(config_path).as_deref()) {
+        Ok(config) => config,
+        Err(report) => {
+            eprintln!("{:?}", report);
+            return ExitCode::FAILURE;
+        }
+    };
+    let options = config.bump_options().clone();
+    let linter = match Linter::from_config(config) {
+        Ok(linter) => linter,
+        Err(report) => {
+            eprintln!("{:?}", report);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // the latest version tag is the current version, without a tag the project starts at 0.0.0
+    let latest_tag = match git::tags(cwd, &args.to) {
+        Ok(tags) => tags
+            .into_iter()
+            .filter_map(|tag| Version::parse(&tag).map(|version| (version, tag)))
+            .max(),
+        Err(report) => {
+            eprintln!("{:?}", report);
+            return ExitCode::FAILURE;
+        }
+    };
+    let version = latest_tag
+        .as_ref()
+        .map(|(version, _)| *version)
+        .unwrap_or_default();
+
+    let from = args
+        .from
+        .as_deref()
+        .or(latest_tag.as_ref().map(|(_, tag)| tag.as_str()));
+    let log = match from {
+        Some(from) => git::commits_in_range(cwd, from, &args.to),
+        None => git::log(cwd, &args.to),
+    };
+    let git_commits = match log {
+        Ok(commits) => commits,
+        Err(report) => {
+            eprintln!("{:?}", report);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // commits which can't be parsed don't require a release
+    let commits: Vec<Commit> = git_commits
+        .iter()
+        .filter_map(|git_commit| linter.parse(&git_commit.message).ok())
+        .collect();
+    let skipped = git_commits.len() - commits.len();
+    if skipped > 0 {
+        eprintln!("Skipped {} commits which could not be parsed", skipped);
+    }
+
+    let bump = version.applied_bump(required_bump(&commits, &options), options.pre_major);
+    if args.bump_only {
+        println!("{}", bump);
+    } else {
+        if bump == Bump::None {
+            eprintln!("No commit requires a release");
+        }
+        // `pre_major` was already applied to the bump
+        println!("{}", version.bump(bump, false));
+    }
+
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,153 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use commitguard::{
+    changelog::{prepend_release, render_release, ChangelogCommit},
+    config, Linter,
+};
+
+use crate::git;
+
+/// Name of the release with the commits which are not released yet
+const UNRELEASED: &str = "Unreleased";
+
+/// Options for generating the changelog
+#[derive(clap::Args, Debug)]
+pub struct ChangelogArgs {
+    /// Only use the commits after this revision (e.g. the tag of the last release), defaults to the whole history
+    #[arg(long)]
+    from: Option<String>,
+
+    /// Last revision of the release
+    #[arg(long, default_value = "HEAD")]
+    to: String,
+
+    /// Version of the release, every version except `Unreleased` gets the date of its last commit
+    #[arg(long, default_value = UNRELEASED)]
+    release: String,
+
+    /// Prepend the release to this changelog file (e.g. `CHANGELOG.md`) instead of printing it
+    #[arg(long)]
+    prepend: Option<PathBuf>,
+}
+
+pub fn run(args: &ChangelogArgs, cwd: &Path, config_path: &Path) -> ExitCode {
+    let config = match config::load_config(config::find_config_file(config_path).as_deref()) {
+        Ok(config) => config,
+        Err(report) => {
+            eprintln!("{:?}", report);
+            return ExitCode::FAILURE;
+        }
+    };
+    let options = config.changelog_options().clone();
+    let linter = match Linter::from_config(config) {
+        Ok(linter) => linter,
+        Err(report) => {
+            eprintln!("{:?}", report);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let log = match &args.from {
+        Some(from) => git::commits_in_range(cwd, from, &args.to),
+        None => git::log(cwd, &args.to),
+    };
+    let git_commits = match log {
+        Ok(commits) => commits,
+        Err(report) => {
+            eprintln!("{:?}", report);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // the changelog lists the newest commits first, commits which can't be parsed are left out
+    let commits: Vec<ChangelogCommit> = git_commits
+        .iter()
+        .rev()
+        .filter_map(|git_commit| {
+            linter
+                .parse(&git_commit.message)
+                .ok()
+                .map(|commit| ChangelogCommit {
+                    hash: git_commit.hash.clone(),
+                    commit,
+                })
+        })
+        .collect();
+    let skipped = git_commits.len() - commits.len();
+    if skipped > 0 {
+        eprintln!("Skipped {} commits which could not be parsed", skipped);
+    }
+
+    let date = match args.release.as_str() {
+        UNRELEASED => None,
+        _ => match git::commit_date(cwd, &args.to) {
+            Ok(date) => Some(date),
+            Err(report) => {
+                eprintln!("{:?}", report);
+                return ExitCode::FAILURE;
+            }
+        },
+    };
+    let release = render_release(&args.release, date.as_deref(), &commits, &options);
+
+    match &args.prepend {
+        Some(path) => {
+            let path = cwd.join(path);
+            let changelog = match read_changelog(&path) {
+                Ok(changelog) => changelog,
+                Err(e) => {
+                    eprintln!("Could not read {}: {}", path.display(), e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            if let Err(e) = fs::write(&path, prepend_release(&changelog, &release)) {
+                eprintln!("Could not write {}: {}", path.display(), e);
+                return ExitCode::FAILURE;
+            }
+        }
+        None => print!("{}", release),
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// Read the changelog file, a missing changelog is created
+fn read_changelog(path: &Path) -> io::Result<String> {
+    match fs::read_to_string(path) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        result => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+
+    use super::*;
+
+    #[test]
+    fn test_read_changelog() {
+        let dir = temp_dir().join(format!("commitguard-changelog-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        assert_eq!(read_changelog(&dir.join("CHANGELOG.md")).unwrap(), "");
+
+        fs::write(dir.join("CHANGELOG.md"), "# Changelog\n").unwrap();
+        assert_eq!(
+            read_changelog(&dir.join("CHANGELOG.md")).unwrap(),
+            "# Changelog\n"
+        );
+
+        // a changelog which can't be read must not be overwritten
+        fs::write(dir.join("CHANGELOG.md"), b"# Changelog\n\xff\xfe\n").unwrap();
+        let error = read_changelog(&dir.join("CHANGELOG.md")).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(read_changelog(&dir).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,62 @@
+use std::{path::Path, process::ExitCode};
+
+use commitguard::{
+    rules::{find_rule_meta, rule_url},
+    Linter,
+};
+
+/// Print the documentation of a rule together with its configured severity
+pub fn run(name: &str, config_path: &Path) -> ExitCode {
+    let Some(meta) = find_rule_meta(name) else {
+        eprintln!(
+            "Unknown rule `{}`, run `commitguard rules` to see all available rules",
+            name
+        );
+        return ExitCode::FAILURE;
+    };
+
+    let linter = match Linter::from_config_file(config_path) {
+        Ok(linter) => linter,
+        Err(report) => {
+            eprintln!("{:?}", report);
+            return ExitCode::FAILURE;
+        }
+    };
+    let severity = linter
+        .rules()
+        .iter()
+        .find(|rule| rule.name() == meta.name)
+        .map(|rule| rule.severity().to_string())
+        .unwrap_or_default();
+
+    println!("{} ({})", meta.name, severity);
+    println!();
+    println!("{}", meta.description);
+    println!();
+    println!("Options: {}", meta.options);
+    println!("Default: {}", meta.default);
+
+    println!();
+    println!("Valid examples:");
+    for example in meta.good {
+        print_example(example);
+    }
+
+    println!();
+    println!("Invalid examples:");
+    for example in meta.bad {
+        print_example(example);
+    }
+
+    println!();
+    println!("More details: {}", rule_url(meta.name));
+
+    ExitCode::SUCCESS
+}
+
+/// Print an indented example commit message
+fn print_example(example: &str) {
+    for line in example.lines() {
+        println!("{}", format!("  {}", line).trim_end());
+    }
+}
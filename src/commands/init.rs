@@ -0,0 +1,348 @@
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use serde_json::Value;
+
+use commitguard::{config, parse_commit, rules::RULES};
+
+use crate::git;
+
+/// Preset which is used to fill the generated config
+#[derive(clap::ValueEnum, Clone, Debug)]
+pub enum Preset {
+    /// Conventional commits with the common types and without restrictions for the scope
+    Conventional,
+    /// Only check the type and the header whitespace, all other rules are turned off
+    Minimal,
+}
+
+/// File format of the generated config
+#[derive(clap::ValueEnum, Clone, Debug)]
+pub enum ConfigFormat {
+    Toml,
+    Json,
+    Yaml,
+}
+
+impl ConfigFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            ConfigFormat::Toml => "toml",
+            ConfigFormat::Json => "json",
+            ConfigFormat::Yaml => "yaml",
+        }
+    }
+
+    /// Format of a config file extension
+    fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "toml" => Some(ConfigFormat::Toml),
+            "json" => Some(ConfigFormat::Json),
+            "yaml" | "yml" => Some(ConfigFormat::Yaml),
+            _ => None,
+        }
+    }
+}
+
+/// Path of the new config file, the extension of the format is appended unless the path already has it
+fn config_file_path(config_path: &Path, format: &ConfigFormat) -> Result<PathBuf, String> {
+    let extension = config_path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .and_then(ConfigFormat::from_extension);
+
+    match extension {
+        Some(extension) if extension.extension() == format.extension() => {
+            Ok(config_path.to_path_buf())
+        }
+        Some(extension) => Err(format!(
+            "{} is a {} file, but the format is {}",
+            config_path.display(),
+            extension.extension(),
+            format.extension()
+        )),
+        None => {
+            let mut path = config_path.as_os_str().to_owned();
+            path.push(".");
+            path.push(format.extension());
+            Ok(PathBuf::from(path))
+        }
+    }
+}
+
+/// Check that no config file would be overwritten without `--force`. Even with `--force`,
+/// any other config file is an error, because it's unclear which of the files would be used.
+fn check_existing_config(config_path: &Path, path: &Path, force: bool) -> Result<(), String> {
+    let existing = config::config_file_candidates(config_path)
+        .into_iter()
+        .filter(|candidate| candidate.is_file())
+        .find(|candidate| !force || candidate != path);
+
+    match existing {
+        Some(existing) if force => Err(format!(
+            "Config file {} already exists, remove it first so that only {} is used",
+            existing.display(),
+            path.display()
+        )),
+        Some(existing) => Err(format!(
+            "Config file {} already exists, use --force to overwrite it",
+            existing.display()
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Options for generating a config file
+#[derive(clap::Args, Debug)]
+pub struct InitArgs {
+    /// Preset which is used to fill the config
+    #[arg(long, value_enum, default_value_t = Preset::Conventional)]
+    preset: Preset,
+
+    /// File format of the config
+    #[arg(long, value_enum, default_value_t = ConfigFormat::Toml)]
+    format: ConfigFormat,
+
+    /// Allow only the types and scopes which are already used in the git history
+    #[arg(long)]
+    infer: bool,
+
+    /// Overwrite an existing config file
+    #[arg(long)]
+    force: bool,
+}
+
+/// Options of the rules which are `off` by default, but which the presets turn on
+const PRESET_OPTIONS: [(&str, &str); 7] = [
+    ("body-leading-blank", r#"["warning", "always"]"#),
+    ("footer-leading-blank", r#"["warning", "always"]"#),
+    ("header-trim", r#"["error", "always"]"#),
+    ("scope-case", r#"["error", "always", "lower-case"]"#),
+    ("subject-full-stop", r#"["error", "never", "."]"#),
+    ("type-case", r#"["error", "always", "lower-case"]"#),
+    (
+        "type-enum",
+        r#"["error", "always", ["build", "chore", "ci", "docs", "feat", "fix", "perf", "refactor", "revert", "style", "test"]]"#,
+    ),
+];
+
+/// Options of all rules for the given preset
+fn preset_rules(preset: &Preset) -> Vec<(&'static str, Value)> {
+    RULES
+        .iter()
+        .map(|meta| {
+            let opts = PRESET_OPTIONS
+                .iter()
+                .find(|(name, _)| *name == meta.name)
+                .map_or(meta.default, |(_, opts)| opts);
+            let mut opts: Value = serde_json::from_str(opts).unwrap();
+            let is_off = match preset {
+                Preset::Conventional => ["scope-empty", "scope-enum"].contains(&meta.name),
+                Preset::Minimal => !["header-trim", "type-enum"].contains(&meta.name),
+            };
+            if is_off {
+                opts[0] = Value::from("off");
+            }
+            (meta.name, opts)
+        })
+        .collect()
+}
+
+/// Collect all types and scopes which are used in the git history
+fn infer_types_and_scopes(cwd: &Path) -> Result<(BTreeSet<String>, BTreeSet<String>), String> {
+    let commits = git::log(cwd, "HEAD").map_err(|report| report.to_string())?;
+
+    let mut types = BTreeSet::new();
+    let mut scopes = BTreeSet::new();
+    for commit in &commits {
+        // commits which don't follow the format are ignored
+        if let Ok(commit) = parse_commit(&commit.message) {
+            types.insert(commit.commit_type.to_string());
+            scopes.extend(commit.scopes.iter().map(|scope| scope.to_string()));
+        }
+    }
+
+    Ok((types, scopes))
+}
+
+/// Render a value on a single line, which is valid in TOML, JSON and YAML
+fn inline(value: &Value) -> String {
+    match value {
+        Value::Array(values) => format!(
+            "[{}]",
+            values.iter().map(inline).collect::<Vec<_>>().join(", ")
+        ),
+        value => value.to_string(),
+    }
+}
+
+/// Render the config in the given format
+fn render(rules: &[(&str, Value)], format: &ConfigFormat) -> String {
+    match format {
+        ConfigFormat::Toml => rules
+            .iter()
+            .fold(String::from("[rules]\n\n"), |config, (name, opts)| {
+                config + format!("{} = {}\n", name, inline(opts)).as_str()
+            }),
+        ConfigFormat::Yaml => rules
+            .iter()
+            .fold(String::from("rules:\n"), |config, (name, opts)| {
+                config + format!("  {}: {}\n", name, inline(opts)).as_str()
+            }),
+        ConfigFormat::Json => {
+            let rules = rules
+                .iter()
+                .map(|(name, opts)| format!("    \"{}\": {}", name, inline(opts)))
+                .collect::<Vec<_>>()
+                .join(",\n");
+            format!("{{\n  \"rules\": {{\n{}\n  }}\n}}\n", rules)
+        }
+    }
+}
+
+/// Write a new config file next to the given config path
+pub fn run(args: &InitArgs, cwd: &Path, config_path: &Path) -> ExitCode {
+    let path = match config_file_path(config_path, &args.format)
+        .and_then(|path| check_existing_config(config_path, &path, args.force).map(|_| path))
+    {
+        Ok(path) => path,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut rules = preset_rules(&args.preset);
+
+    if args.infer {
+        let (types, scopes) = match infer_types_and_scopes(cwd) {
+            Ok(found) => found,
+            Err(e) => {
+                eprintln!("Could not read the git history: {}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+
+        for (name, opts) in rules.iter_mut() {
+            let found = match *name {
+                "type-enum" => &types,
+                "scope-enum" => &scopes,
+                _ => continue,
+            };
+            if !found.is_empty() {
+                *opts = Value::from(vec![
+                    Value::from("error"),
+                    Value::from("always"),
+                    Value::from(found.iter().cloned().collect::<Vec<_>>()),
+                ]);
+            }
+        }
+    }
+
+    if let Err(e) = fs::write(&path, render(&rules, &args.format)) {
+        eprintln!("Could not write {}: {}", path.display(), e);
+        return ExitCode::FAILURE;
+    }
+    println!("Created {}", path.display());
+
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+
+    use commitguard::{rules::Severity, Linter};
+
+    use super::*;
+
+    #[test]
+    fn test_rendered_configs_can_be_loaded() {
+        let dir = temp_dir().join(format!("commitguard-init-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        for format in [ConfigFormat::Toml, ConfigFormat::Json, ConfigFormat::Yaml] {
+            let path = dir.join(format!("commitguard.config.{}", format.extension()));
+            fs::write(&path, render(&preset_rules(&Preset::Minimal), &format)).unwrap();
+
+            let linter = config::load_config(Some(&path))
+                .and_then(Linter::from_config)
+                .unwrap();
+            let is_off = |name: &str| {
+                linter
+                    .rules()
+                    .iter()
+                    .find(|rule| rule.name() == name)
+                    .map(|rule| rule.severity() == &Severity::Off)
+            };
+            assert_eq!(is_off("type-enum"), Some(false), "{:?}", format);
+            assert_eq!(is_off("header-trim"), Some(false), "{:?}", format);
+            assert_eq!(is_off("scope-case"), Some(true), "{:?}", format);
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_conventional_preset() {
+        let rules = preset_rules(&Preset::Conventional);
+        let severity = |name: &str| {
+            rules
+                .iter()
+                .find(|(rule, _)| *rule == name)
+                .map(|(_, opts)| opts[0].as_str().unwrap().to_string())
+                .unwrap()
+        };
+
+        // the rules with automatic fixes are `off` by default, but turned on by the preset
+        assert_eq!(severity("type-case"), "error");
+        assert_eq!(severity("subject-full-stop"), "error");
+        assert_eq!(severity("body-leading-blank"), "warning");
+        assert_eq!(severity("type-enum"), "error");
+        assert_eq!(severity("scope-empty"), "off");
+    }
+
+    #[test]
+    fn test_config_file_path() {
+        let path = |config_path: &str, format| {
+            config_file_path(Path::new(config_path), &format).map(|path| path.display().to_string())
+        };
+
+        assert_eq!(
+            path("commitguard.config", ConfigFormat::Toml).unwrap(),
+            "commitguard.config.toml"
+        );
+        assert_eq!(
+            path("commitguard.config.json", ConfigFormat::Json).unwrap(),
+            "commitguard.config.json"
+        );
+        assert_eq!(
+            path("commitguard.config.yml", ConfigFormat::Yaml).unwrap(),
+            "commitguard.config.yml"
+        );
+        assert!(path("commitguard.config.json", ConfigFormat::Toml).is_err());
+    }
+
+    #[test]
+    fn test_check_existing_config() {
+        let dir = temp_dir().join(format!("commitguard-init-existing-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("commitguard.config");
+        let toml = dir.join("commitguard.config.toml");
+        let json = dir.join("commitguard.config.json");
+
+        assert!(check_existing_config(&config_path, &json, false).is_ok());
+
+        fs::write(&toml, "").unwrap();
+        assert!(check_existing_config(&config_path, &toml, false).is_err());
+        assert!(check_existing_config(&config_path, &toml, true).is_ok());
+        // it's unclear whether the TOML or the new JSON file would be used
+        assert!(check_existing_config(&config_path, &json, true).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
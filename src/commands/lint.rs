@@ -0,0 +1,517 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    fmt::Write,
+    fs,
+    io::{stdin, stdout, IsTerminal, Read},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use miette::{GraphicalReportHandler, GraphicalTheme};
+use similar::TextDiff;
+
+use commitguard::{
+    config,
+    rules::{self, Severity},
+    LintContext, LintResult, Linter,
+};
+
+use crate::git;
+
+/// When to use colors in the output
+#[derive(clap::ValueEnum, Clone, Debug)]
+pub enum ColorChoice {
+    /// Use colors if the output is a terminal and `NO_COLOR` is not set
+    Auto,
+    /// Always use colors
+    Always,
+    /// Never use colors
+    Never,
+}
+
+/// Options for linting commit messages
+#[derive(clap::Args, Debug)]
+pub struct LintArgs {
+    /// Only print output if linting fails
+    #[arg(short, long, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Print the used config file, the evaluated rules and the rules which passed
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Only print the number of violations per rule instead of every report
+    #[arg(short, long)]
+    summary: bool,
+
+    /// When to use colors in the output
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+
+    /// Read the commit message from this file instead of stdin (e.g. `.git/COMMIT_EDITMSG`)
+    #[arg(short, long, conflicts_with = "from")]
+    edit: Option<PathBuf>,
+
+    /// Fix violations automatically and write the result back to the `--edit` file or print it to stdout
+    #[arg(long, conflicts_with = "from")]
+    fix: bool,
+
+    /// Print a diff of the automatic fixes without applying them
+    #[arg(long, conflicts_with_all = ["from", "fix"])]
+    fix_dry_run: bool,
+
+    /// Lint all commits after this revision instead of reading the commit message from stdin
+    #[arg(long)]
+    from: Option<String>,
+
+    /// Last revision of the range to lint
+    #[arg(long, default_value = "HEAD", requires = "from")]
+    to: String,
+
+    /// Identity which the `Signed-off-by` trailer must match (e.g. `Jane Doe <jane@example.com>`),
+    /// defaults to the author of each commit with `--from`
+    #[arg(long)]
+    author: Option<String>,
+
+    /// Files which the commit changes for the `scope-matches-paths` rule (comma-separated),
+    /// defaults to the staged files or the files of each commit with `--from`
+    #[arg(long, value_delimiter = ',', conflicts_with = "from")]
+    changed_files: Option<Vec<String>>,
+}
+
+/// A commit message which is linted
+struct Message {
+    /// Abbreviated hash, if the message was read from the git history
+    hash: Option<String>,
+    /// Identity of the author, if it is known
+    author: Option<String>,
+    /// Files which the commit changes, if they are known
+    changed_files: Option<Vec<String>>,
+    text: String,
+}
+
+/// Number of warnings and errors of a single rule
+#[derive(Default)]
+struct RuleCount {
+    warnings: usize,
+    errors: usize,
+}
+
+/// Count the reports of a lint result per rule code
+fn count_per_rule(summary: &mut BTreeMap<String, RuleCount>, lint_result: &LintResult) {
+    let code = |report: &miette::Report| {
+        report
+            .code()
+            .map(|code| code.to_string())
+            .unwrap_or_default()
+    };
+
+    for report in lint_result.warnings().into_iter().flatten() {
+        summary.entry(code(report)).or_default().warnings += 1;
+    }
+    for report in lint_result.errors().into_iter().flatten() {
+        summary.entry(code(report)).or_default().errors += 1;
+    }
+}
+
+/// Handler which renders the reports with or without colors
+fn report_handler(color: &ColorChoice) -> GraphicalReportHandler {
+    let color = match color {
+        ColorChoice::Auto => stdout().is_terminal() && env::var_os("NO_COLOR").is_none(),
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+    };
+
+    match color {
+        true => GraphicalReportHandler::new_themed(GraphicalTheme::unicode()),
+        // terminals without colors and log files can't render hyperlinks either
+        false => {
+            GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor()).with_links(false)
+        }
+    }
+}
+
+/// Print all reports of a single lint result, it fails if there are errors
+pub fn print_result(lint_result: &LintResult, color: &ColorChoice) -> ExitCode {
+    let report_handler = report_handler(color);
+    let mut out = String::new();
+
+    for report in lint_result
+        .warnings()
+        .into_iter()
+        .flatten()
+        .chain(lint_result.errors().into_iter().flatten())
+    {
+        out.push('\n');
+        let _ = report_handler.render_report(&mut out, report.as_ref());
+    }
+    if lint_result.has_warnings() || lint_result.has_errors() {
+        out.push('\n');
+    }
+    let _ = writeln!(
+        out,
+        "There are {} warnings and {} errors",
+        lint_result.warnings_len(),
+        lint_result.errors_len()
+    );
+    print!("{}", out);
+
+    match lint_result.has_errors() {
+        true => ExitCode::FAILURE,
+        false => ExitCode::SUCCESS,
+    }
+}
+
+/// Check if a rule of the linter (or an override for a commit type) needs the changed files,
+/// so that they have to be read from git
+pub fn needs_changed_files(linter: &Linter) -> bool {
+    linter
+        .rules()
+        .iter()
+        .chain(linter.type_overrides().values().flatten())
+        .any(|rule| {
+            rule.name() == rules::scope_matches_paths::META.name
+                && rule.severity() != &Severity::Off
+        })
+}
+
+/// Render the reports of all lint results, nothing is rendered with `--quiet` if there are no errors
+fn render_results(
+    lint_results: &[(Option<String>, LintResult)],
+    args: &LintArgs,
+) -> Option<String> {
+    let warnings_len: usize = lint_results.iter().map(|(_, r)| r.warnings_len()).sum();
+    let errors_len: usize = lint_results.iter().map(|(_, r)| r.errors_len()).sum();
+
+    if args.quiet && errors_len == 0 {
+        return None;
+    }
+
+    let report_handler = report_handler(&args.color);
+    let mut out = String::new();
+
+    let mut summary = BTreeMap::new();
+    for (hash, lint_result) in lint_results {
+        count_per_rule(&mut summary, lint_result);
+
+        if args.summary {
+            continue;
+        }
+
+        if let Some(hash) = hash {
+            if args.verbose || lint_result.has_warnings() || lint_result.has_errors() {
+                let _ = writeln!(out, "commit {}", hash);
+            }
+        }
+
+        if args.verbose {
+            let _ = writeln!(
+                out,
+                "Evaluated rules: {}",
+                lint_result.evaluated().join(", ")
+            );
+            let _ = writeln!(out, "Passed rules: {}", lint_result.passed().join(", "));
+        }
+
+        if lint_result.has_warnings() {
+            lint_result.warnings().unwrap().iter().for_each(|report| {
+                out.push('\n');
+                let _ = report_handler.render_report(&mut out, report.as_ref());
+            });
+            out.push('\n');
+        }
+
+        if lint_result.has_errors() {
+            lint_result.errors().unwrap().iter().for_each(|report| {
+                out.push('\n');
+                let _ = report_handler.render_report(&mut out, report.as_ref());
+            });
+            out.push('\n');
+        }
+    }
+
+    if args.summary {
+        for (code, count) in &summary {
+            let _ = writeln!(
+                out,
+                "{}: {} warnings and {} errors",
+                code, count.warnings, count.errors
+            );
+        }
+    }
+
+    let _ = writeln!(
+        out,
+        "There are {} warnings and {} errors",
+        warnings_len, errors_len
+    );
+
+    Some(out)
+}
+
+pub fn run(args: &LintArgs, cwd: &Path, config_path: &Path) -> ExitCode {
+    // all output is collected first, because it is printed to stderr if the fixed message goes to stdout
+    let mut out = String::new();
+
+    let config_file = config::find_config_file(config_path);
+    if args.verbose {
+        let _ = match &config_file {
+            Some(config_file) => writeln!(out, "Using config file {}", config_file.display()),
+            None => writeln!(
+                out,
+                "No config file found at {}, using the default config",
+                config_path.display()
+            ),
+        };
+    }
+    let linter = match config::load_config(config_file.as_deref()).and_then(Linter::from_config) {
+        Ok(linter) => linter,
+        Err(report) => {
+            eprintln!("{:?}", report);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // the changed files are only read from git if a rule needs them, git errors leave them unknown
+    let needs_changed_files = needs_changed_files(&linter);
+    let staged_files = || match &args.changed_files {
+        Some(changed_files) => Some(changed_files.clone()),
+        None if needs_changed_files => git::staged_files(cwd).ok(),
+        None => None,
+    };
+
+    // read the commit messages either from the git history, the edit file or stdin
+    let mut messages: Vec<Message> = match &args.from {
+        Some(from) => match git::commits_in_range(cwd, from, &args.to) {
+            Ok(commits) => commits
+                .into_iter()
+                .map(|commit| Message {
+                    hash: Some(commit.short_hash().to_string()),
+                    changed_files: needs_changed_files
+                        .then(|| git::changed_files(cwd, &commit.hash).ok())
+                        .flatten(),
+                    author: Some(args.author.clone().unwrap_or(commit.author)),
+                    text: commit.message,
+                })
+                .collect(),
+            Err(report) => {
+                eprintln!("{:?}", report);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => match &args.edit {
+            Some(edit) => match fs::read_to_string(edit) {
+                Ok(text) => vec![Message {
+                    hash: None,
+                    author: args.author.clone(),
+                    changed_files: staged_files(),
+                    text,
+                }],
+                Err(e) => {
+                    eprintln!("Could not read {}: {}", edit.display(), e);
+                    return ExitCode::FAILURE;
+                }
+            },
+            None => {
+                let mut buffer = String::new();
+                stdin().read_to_string(&mut buffer).unwrap_or(0);
+                vec![Message {
+                    hash: None,
+                    author: args.author.clone(),
+                    changed_files: staged_files(),
+                    text: buffer,
+                }]
+            }
+        },
+    };
+
+    // when fixing to stdout, the fixed message is the only output on stdout and the reports go to stderr
+    let mut print_to_stderr = false;
+    if args.fix || args.fix_dry_run {
+        let message = &messages[0].text;
+        let fixed = linter.fix(message);
+
+        if args.fix_dry_run {
+            print!(
+                "{}",
+                TextDiff::from_lines(message, &fixed)
+                    .unified_diff()
+                    .header("original", "fixed")
+            );
+            return if &fixed == message {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            };
+        }
+
+        match &args.edit {
+            Some(edit) => {
+                if let Err(e) = fs::write(edit, &fixed) {
+                    eprintln!("Could not write {}: {}", edit.display(), e);
+                    return ExitCode::FAILURE;
+                }
+            }
+            None => {
+                print!("{}", fixed);
+                print_to_stderr = true;
+            }
+        }
+        messages[0].text = fixed;
+    }
+
+    let lint_results: Vec<(Option<String>, LintResult)> = messages
+        .iter()
+        .map(|message| {
+            let context = LintContext {
+                author: message.author.clone(),
+                changed_files: message.changed_files.clone(),
+            };
+            (
+                message.hash.clone(),
+                linter.lint_with_context(&message.text, &context),
+            )
+        })
+        .collect();
+
+    match render_results(&lint_results, args) {
+        Some(results) => out.push_str(&results),
+        None => return ExitCode::SUCCESS,
+    }
+
+    if print_to_stderr {
+        eprint!("{}", out);
+    } else {
+        print!("{}", out);
+    }
+
+    match lint_results
+        .iter()
+        .any(|(_, lint_result)| lint_result.has_errors())
+    {
+        true => ExitCode::FAILURE,
+        false => ExitCode::SUCCESS,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+    use commitguard::config::RulesConfig;
+
+    use super::*;
+
+    #[derive(clap::Parser)]
+    struct Cli {
+        #[command(flatten)]
+        lint: LintArgs,
+    }
+
+    fn args(flags: &[&str]) -> LintArgs {
+        Cli::parse_from(["commitguard"].iter().chain(flags)).lint
+    }
+
+    /// Results of a commit without violations, one with a warning and one with an error
+    fn lint_results() -> Vec<(Option<String>, LintResult)> {
+        let linter = Linter::from_config(
+            RulesConfig::from_toml(
+                r#"
+                [rules]
+                type-enum = ["error", "always", ["feat"]]
+                subject-full-stop = ["warning", "never", "."]
+                "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        [
+            "feat(foo): add feature",
+            "feat(foo): add feature.",
+            "fix(foo): fix bug",
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, message)| (Some(format!("abc{}", i)), linter.lint(message)))
+        .collect()
+    }
+
+    #[test]
+    fn test_count_per_rule() {
+        let mut summary = BTreeMap::new();
+        for (_, lint_result) in lint_results() {
+            count_per_rule(&mut summary, &lint_result);
+        }
+
+        let counts: Vec<_> = summary
+            .iter()
+            .map(|(code, count)| (code.as_str(), count.warnings, count.errors))
+            .collect();
+        assert_eq!(
+            counts,
+            [("rule/subject-full-stop", 1, 0), ("rule/type-enum", 0, 1)]
+        );
+    }
+
+    #[test]
+    fn test_quiet() {
+        let lint_results = lint_results();
+        let args = args(&["--quiet", "--color", "never"]);
+
+        // warnings alone are not printed
+        assert_eq!(render_results(&lint_results[..2], &args), None);
+
+        let out = render_results(&lint_results, &args).unwrap();
+        assert!(out.contains("commit abc2"));
+        assert!(out.ends_with("There are 1 warnings and 1 errors\n"));
+    }
+
+    #[test]
+    fn test_summary() {
+        let out =
+            render_results(&lint_results(), &args(&["--summary", "--color", "never"])).unwrap();
+
+        assert_eq!(
+            out,
+            "rule/subject-full-stop: 1 warnings and 0 errors\nrule/type-enum: 0 warnings and 1 errors\nThere are 1 warnings and 1 errors\n"
+        );
+    }
+
+    #[test]
+    fn test_no_links_without_colors() {
+        let out = render_results(&lint_results(), &args(&["--color", "never"])).unwrap();
+
+        assert!(out.contains("rule/type-enum"));
+        assert!(!out.contains('\u{1b}'));
+    }
+
+    #[test]
+    fn test_needs_changed_files() {
+        let linter = |config: &str| Linter::from_config(RulesConfig::from_toml(config).unwrap());
+
+        assert!(!needs_changed_files(&linter("").unwrap()));
+        assert!(needs_changed_files(
+            &linter(
+                r#"
+                [rules]
+                scope-matches-paths = ["error", { api = ["packages/api/**"] }]
+                "#
+            )
+            .unwrap()
+        ));
+        // the rule is only turned on for commits of a type
+        assert!(needs_changed_files(
+            &linter(
+                r#"
+                [rules]
+                scope-matches-paths = ["off", { api = ["packages/api/**"] }]
+
+                [rules.when.type.feat]
+                scope-matches-paths = ["error"]
+                "#
+            )
+            .unwrap()
+        ));
+    }
+}
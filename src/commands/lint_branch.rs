@@ -0,0 +1,40 @@
+use std::{path::Path, process::ExitCode};
+
+use commitguard::{branch::lint_branch, config};
+
+use super::lint::{print_result, ColorChoice};
+use crate::git;
+
+/// Options for linting the name of a branch
+#[derive(clap::Args, Debug)]
+pub struct LintBranchArgs {
+    /// Name of the branch, defaults to the current branch
+    branch: Option<String>,
+
+    /// When to use colors in the output
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+}
+
+pub fn run(args: &LintBranchArgs, cwd: &Path, config_path: &Path) -> ExitCode {
+    let config = match config::load_config(config::find_config_file(config_path).as_deref()) {
+        Ok(config) => config,
+        Err(report) => {
+            eprintln!("{:?}", report);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let branch = match &args.branch {
+        Some(branch) => branch.clone(),
+        None => match git::current_branch(cwd) {
+            Ok(branch) => branch,
+            Err(report) => {
+                eprintln!("{:?}", report);
+                return ExitCode::FAILURE;
+            }
+        },
+    };
+
+    print_result(&lint_branch(&branch, config.branch_options()), &args.color)
+}
@@ -0,0 +1,30 @@
+use std::{path::Path, process::ExitCode};
+
+use commitguard::{config, Linter};
+
+use super::lint::{print_result, ColorChoice};
+
+/// Options for linting the title of a pull request
+#[derive(clap::Args, Debug)]
+pub struct LintTitleArgs {
+    /// Title which becomes the header of the squashed commit (e.g. `feat(api): add endpoint`)
+    title: String,
+
+    /// When to use colors in the output
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+}
+
+pub fn run(args: &LintTitleArgs, config_path: &Path) -> ExitCode {
+    let linter = match config::load_config(config::find_config_file(config_path).as_deref())
+        .and_then(Linter::from_config)
+    {
+        Ok(linter) => linter,
+        Err(report) => {
+            eprintln!("{:?}", report);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    print_result(&linter.lint_title(&args.title), &args.color)
+}
@@ -0,0 +1,518 @@
+use std::{collections::HashMap, path::Path, process::ExitCode};
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+        Notification as NotificationMethod, PublishDiagnostics,
+    },
+    request::{CodeActionRequest, Completion, Request as RequestMethod},
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams,
+    CodeActionProviderCapability, CompletionItem, CompletionItemKind, CompletionOptions,
+    CompletionParams, Diagnostic, DiagnosticSeverity, DidChangeTextDocumentParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, NumberOrString,
+    PublishDiagnosticsParams, Range, ServerCapabilities, TextDocumentSyncCapability,
+    TextDocumentSyncKind, TextEdit, Uri, WorkspaceEdit,
+};
+use miette::Report;
+use serde::de::DeserializeOwned;
+
+use commitguard::{config, rules::TypedValues, LineIndex, Linter};
+
+/// Name of the server in the diagnostics
+const SOURCE: &str = "commitguard";
+
+/// State of the language server
+struct Server {
+    linter: Linter,
+    types: Vec<String>,
+    scopes: TypedValues,
+    /// Text of every open document
+    documents: HashMap<Uri, String>,
+}
+
+impl Server {
+    /// Reply to a request of the client
+    fn handle_request(&self, request: Request) -> Response {
+        match request.method.as_str() {
+            CodeActionRequest::METHOD => match params::<CodeActionParams>(request.params) {
+                Ok(params) => Response::new_ok(request.id, self.code_actions(&params)),
+                Err(e) => Response::new_err(request.id, ErrorCode::InvalidParams as i32, e),
+            },
+            Completion::METHOD => match params::<CompletionParams>(request.params) {
+                Ok(params) => Response::new_ok(request.id, self.completions(&params)),
+                Err(e) => Response::new_err(request.id, ErrorCode::InvalidParams as i32, e),
+            },
+            method => Response::new_err(
+                request.id,
+                ErrorCode::MethodNotFound as i32,
+                format!("Unknown method {}", method),
+            ),
+        }
+    }
+
+    /// Update the documents and return the diagnostics of the changed document
+    fn handle_notification(
+        &mut self,
+        notification: Notification,
+    ) -> Option<PublishDiagnosticsParams> {
+        let uri = match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams = params(notification.params).ok()?;
+                let document = params.text_document;
+                self.documents.insert(document.uri.clone(), document.text);
+                document.uri
+            }
+            DidChangeTextDocument::METHOD => {
+                let mut params: DidChangeTextDocumentParams = params(notification.params).ok()?;
+                // the whole document is sent on every change
+                let change = params.content_changes.pop()?;
+                let uri = params.text_document.uri;
+                self.documents.insert(uri.clone(), change.text);
+                uri
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams = params(notification.params).ok()?;
+                let uri = params.text_document.uri;
+                self.documents.remove(&uri);
+                return Some(PublishDiagnosticsParams::new(uri, vec![], None));
+            }
+            _ => return None,
+        };
+
+        let diagnostics = self.diagnostics(&self.documents[&uri]);
+        Some(PublishDiagnosticsParams::new(uri, diagnostics, None))
+    }
+
+    /// Lint the commit message of the document
+    fn diagnostics(&self, document: &str) -> Vec<Diagnostic> {
+        let text = message_text(document);
+        // git aborts the commit if the message is empty
+        if text.trim().is_empty() {
+            return vec![];
+        }
+
+        let result = self.linter.lint(text);
+        let index = LineIndex::new(text);
+        result
+            .errors()
+            .into_iter()
+            .flatten()
+            .chain(result.warnings().into_iter().flatten())
+            .flat_map(|report| to_diagnostics(report, &index))
+            .collect()
+    }
+
+    /// A quick fix for every rule with fixes and a fix of all violations
+    fn code_actions(&self, params: &CodeActionParams) -> Vec<CodeActionOrCommand> {
+        let uri = &params.text_document.uri;
+        let Some(document) = self.documents.get(uri) else {
+            return vec![];
+        };
+        let text = message_text(document);
+        let index = LineIndex::new(text);
+        let edit = |edits: Vec<TextEdit>| WorkspaceEdit {
+            changes: Some(HashMap::from([(uri.clone(), edits)])),
+            ..Default::default()
+        };
+
+        let mut actions = vec![];
+        for (name, fixes) in self.linter.fixes(text) {
+            let code = NumberOrString::String(format!("rule/{}", name));
+            let diagnostics: Vec<Diagnostic> = params
+                .context
+                .diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.code.as_ref() == Some(&code))
+                .cloned()
+                .collect();
+            if diagnostics.is_empty() {
+                continue;
+            }
+
+            let edits = fixes
+                .into_iter()
+                .map(|fix| TextEdit::new(to_range(&index, fix.start, fix.end), fix.replacement))
+                .collect();
+            actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                title: format!("Fix {}", name),
+                kind: Some(CodeActionKind::QUICKFIX),
+                diagnostics: Some(diagnostics),
+                edit: Some(edit(edits)),
+                is_preferred: Some(true),
+                ..Default::default()
+            }));
+        }
+
+        let fixed = self.linter.fix(text);
+        if fixed != text {
+            actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                title: String::from("Fix all commitguard violations"),
+                kind: Some(CodeActionKind::SOURCE_FIX_ALL),
+                edit: Some(edit(vec![TextEdit::new(
+                    to_range(&index, 0, text.len()),
+                    fixed,
+                )])),
+                ..Default::default()
+            }));
+        }
+
+        actions
+    }
+
+    /// Types and scopes for the position in the header
+    fn completions(&self, params: &CompletionParams) -> Vec<CompletionItem> {
+        let position = &params.text_document_position;
+        let Some(document) = self.documents.get(&position.text_document.uri) else {
+            return vec![];
+        };
+        if position.position.line != 0 {
+            return vec![];
+        }
+
+        let header = document.lines().next().unwrap_or_default();
+        let prefix = utf16_prefix(header, position.position.character);
+        header_completions(prefix, &self.types, &self.scopes)
+    }
+}
+
+/// Deserialize the params of a request or notification
+fn params<P: DeserializeOwned>(params: serde_json::Value) -> Result<P, String> {
+    serde_json::from_value(params).map_err(|e| e.to_string())
+}
+
+/// The commit message of a document, without the comments which git removes
+/// (e.g. the status at the end of `COMMIT_EDITMSG`)
+fn message_text(document: &str) -> &str {
+    let mut end = 0;
+    for line in document.split_inclusive('\n') {
+        if line.starts_with('#') {
+            break;
+        }
+        end += line.len();
+    }
+
+    document[..end].trim_end_matches(['\n', '\r'])
+}
+
+/// The part of the line in front of the UTF-16 column
+fn utf16_prefix(line: &str, column: u32) -> &str {
+    let mut units = 0;
+    for (offset, c) in line.char_indices() {
+        if units >= column as usize {
+            return &line[..offset];
+        }
+        units += c.len_utf16();
+    }
+
+    line
+}
+
+/// LSP range of the byte offsets
+fn to_range(index: &LineIndex, start: usize, end: usize) -> Range {
+    let position = |offset| {
+        let position = index.position(offset);
+        lsp_types::Position::new(
+            (position.line - 1) as u32,
+            (position.utf16_column - 1) as u32,
+        )
+    };
+
+    Range::new(position(start), position(end))
+}
+
+/// A diagnostic for every label of the report, or one at the start if it has no labels
+fn to_diagnostics(report: &Report, index: &LineIndex) -> Vec<Diagnostic> {
+    let severity = match report.severity() {
+        Some(miette::Severity::Warning) => DiagnosticSeverity::WARNING,
+        Some(miette::Severity::Advice) => DiagnosticSeverity::HINT,
+        Some(miette::Severity::Error) | None => DiagnosticSeverity::ERROR,
+    };
+    let diagnostic = |range, label: Option<&str>| {
+        let mut message = match label {
+            Some(label) => format!("{}: {}", report, label),
+            None => report.to_string(),
+        };
+        if let Some(help) = report.help() {
+            message += format!("\nhelp: {}", help).as_str();
+        }
+
+        Diagnostic {
+            range,
+            severity: Some(severity),
+            code: report
+                .code()
+                .map(|code| NumberOrString::String(code.to_string())),
+            source: Some(String::from(SOURCE)),
+            message,
+            ..Default::default()
+        }
+    };
+
+    match report.labels() {
+        Some(labels) => labels
+            .map(|label| {
+                diagnostic(
+                    to_range(index, label.offset(), label.offset() + label.len()),
+                    label.label(),
+                )
+            })
+            .collect(),
+        None => vec![diagnostic(to_range(index, 0, 0), None)],
+    }
+}
+
+/// Types in front of the scope and scopes of the typed type inside the braces
+fn header_completions(prefix: &str, types: &[String], scopes: &TypedValues) -> Vec<CompletionItem> {
+    let item = |label: &String, kind, detail: &str| CompletionItem {
+        label: label.clone(),
+        kind: Some(kind),
+        detail: Some(String::from(detail)),
+        ..Default::default()
+    };
+
+    if prefix.contains([')', '!', ':']) {
+        return vec![];
+    }
+
+    match prefix.split_once('(') {
+        // an emoji can be in front of the type
+        Some((commit_type, _)) => {
+            let commit_type = commit_type.split_whitespace().last().unwrap_or_default();
+            scopes
+                .for_type(commit_type)
+                .iter()
+                .map(|scope| item(scope, CompletionItemKind::MODULE, "scope"))
+                .collect()
+        }
+        None => types
+            .iter()
+            .map(|commit_type| item(commit_type, CompletionItemKind::KEYWORD, "type"))
+            .collect(),
+    }
+}
+
+pub fn run(config_path: &Path) -> ExitCode {
+    let config = match config::load_config(config::find_config_file(config_path).as_deref()) {
+        Ok(config) => config,
+        Err(report) => {
+            eprintln!("{:?}", report);
+            return ExitCode::FAILURE;
+        }
+    };
+    let types = config.allowed_types();
+    let scopes = config.allowed_scopes();
+    let linter = match Linter::from_config(config) {
+        Ok(linter) => linter,
+        Err(report) => {
+            eprintln!("{:?}", report);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let (connection, io_threads) = Connection::stdio();
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec![String::from("("), String::from(",")]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    if let Err(e) = connection.initialize(serde_json::to_value(capabilities).unwrap()) {
+        eprintln!("Could not initialize the language server: {}", e);
+        return ExitCode::FAILURE;
+    }
+
+    let mut server = Server {
+        linter,
+        types,
+        scopes,
+        documents: HashMap::new(),
+    };
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request).unwrap_or(true) {
+                    break;
+                }
+                let response = server.handle_request(request);
+                let _ = connection.sender.send(Message::Response(response));
+            }
+            Message::Notification(notification) => {
+                if let Some(diagnostics) = server.handle_notification(notification) {
+                    let _ = connection
+                        .sender
+                        .send(Message::Notification(Notification::new(
+                            PublishDiagnostics::METHOD.to_string(),
+                            diagnostics,
+                        )));
+                }
+            }
+            Message::Response(_) => {}
+        }
+    }
+
+    // the connection has to be dropped first, so that the writer thread can stop
+    drop(connection);
+    if let Err(e) = io_threads.join() {
+        eprintln!("Language server stopped with an error: {}", e);
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use commitguard::config::RulesConfig;
+
+    use super::*;
+
+    fn server(document: &str) -> (Server, Uri) {
+        let uri: Uri = "file:///repo/.git/COMMIT_EDITMSG".parse().unwrap();
+        let server = Server {
+            linter: Linter::from_config(
+                RulesConfig::from_toml(
+                    r#"
+                    [rules]
+                    subject-full-stop = ["error", "never", "."]
+                    type-case = ["error", "always", "lower-case"]
+                    "#,
+                )
+                .unwrap(),
+            )
+            .unwrap(),
+            types: vec![],
+            scopes: TypedValues::default(),
+            documents: HashMap::from([(uri.clone(), document.to_string())]),
+        };
+
+        (server, uri)
+    }
+
+    fn labels(items: &[CompletionItem]) -> Vec<&str> {
+        items.iter().map(|item| item.label.as_str()).collect()
+    }
+
+    #[test]
+    fn test_message_text() {
+        assert_eq!(
+            message_text(
+                "feat: add feature\n\nsome body\n\n# Please enter the commit message\n#\n"
+            ),
+            "feat: add feature\n\nsome body"
+        );
+        assert_eq!(message_text("feat: add feature"), "feat: add feature");
+        assert_eq!(message_text("# only comments\n"), "");
+    }
+
+    #[test]
+    fn test_utf16_prefix() {
+        assert_eq!(utf16_prefix("feat(api): add", 5), "feat(");
+        assert_eq!(utf16_prefix("😀 feat(", 3), "😀 ");
+        assert_eq!(utf16_prefix("feat", 10), "feat");
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let (server, uri) = server("");
+        let document = "feat(foo): add feature.\n\n# comment";
+        let diagnostics = server.diagnostics(document);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code,
+            Some(NumberOrString::String(String::from(
+                "rule/subject-full-stop"
+            )))
+        );
+        assert_eq!(
+            diagnostics[0].range,
+            Range::new(
+                lsp_types::Position::new(0, 22),
+                lsp_types::Position::new(0, 23)
+            )
+        );
+        assert!(diagnostics[0]
+            .message
+            .contains(": not allowed full stop\nhelp: "));
+
+        assert!(server.diagnostics(&server.documents[&uri]).is_empty());
+        assert!(server
+            .diagnostics("# Please enter the commit message")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_code_actions() {
+        let document = "FEAT(foo): add feature.\n\n# comment";
+        let (server, uri) = server(document);
+        let diagnostics = server.diagnostics(document);
+        let params: CodeActionParams = serde_json::from_value(serde_json::json!({
+            "textDocument": { "uri": uri },
+            "range": { "start": { "line": 0, "character": 0 }, "end": { "line": 0, "character": 0 } },
+            "context": { "diagnostics": diagnostics },
+        }))
+        .unwrap();
+
+        let actions: Vec<CodeAction> = server
+            .code_actions(&params)
+            .into_iter()
+            .filter_map(|action| match action {
+                CodeActionOrCommand::CodeAction(action) => Some(action),
+                CodeActionOrCommand::Command(_) => None,
+            })
+            .collect();
+        let titles: Vec<_> = actions.iter().map(|action| action.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            [
+                "Fix subject-full-stop",
+                "Fix type-case",
+                "Fix all commitguard violations"
+            ]
+        );
+
+        let edits = &actions[2].edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri];
+        assert_eq!(edits[0].new_text, "feat(foo): add feature");
+        assert_eq!(
+            edits[0].range,
+            Range::new(
+                lsp_types::Position::new(0, 0),
+                lsp_types::Position::new(0, 23)
+            )
+        );
+    }
+
+    #[test]
+    fn test_header_completions() {
+        let types = vec![String::from("feat"), String::from("fix")];
+        let scopes = TypedValues::ByType(BTreeMap::from([(
+            String::from("feat"),
+            vec![String::from("api"), String::from("ui")],
+        )]));
+
+        assert_eq!(
+            labels(&header_completions("", &types, &scopes)),
+            ["feat", "fix"]
+        );
+        assert_eq!(
+            labels(&header_completions("✨ fe", &types, &scopes)),
+            ["feat", "fix"]
+        );
+        assert_eq!(
+            labels(&header_completions("feat(", &types, &scopes)),
+            ["api", "ui"]
+        );
+        assert_eq!(
+            labels(&header_completions("feat(api,", &types, &scopes)),
+            ["api", "ui"]
+        );
+        assert!(header_completions("fix(", &types, &scopes).is_empty());
+        assert!(header_completions("feat(api): ", &types, &scopes).is_empty());
+    }
+}
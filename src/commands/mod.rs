@@ -0,0 +1,10 @@
+pub mod bump;
+pub mod changelog;
+pub mod explain;
+pub mod init;
+pub mod lint;
+pub mod lint_branch;
+pub mod lint_title;
+pub mod lsp;
+pub mod prompt;
+pub mod rules;
@@ -0,0 +1,474 @@
+use std::{path::Path, process::ExitCode, rc::Rc};
+
+use inquire::{validator::Validation, Confirm, InquireError, Select, Text};
+
+use commitguard::{config, rules::TypedValues, LintContext, Linter};
+
+use crate::{commands::lint::needs_changed_files, git};
+
+/// Placeholder for the subject while earlier parts of the message are validated
+const SUBJECT_PLACEHOLDER: &str = "subject";
+
+/// Label of the option to leave out the scope
+const NO_SCOPE: &str = "(none)";
+
+/// Options for the interactive prompt
+#[derive(clap::Args, Debug)]
+pub struct PromptArgs {
+    /// Commit the staged changes with the message instead of printing it
+    #[arg(long)]
+    commit: bool,
+}
+
+/// Part of the commit message which is asked for
+#[derive(Clone, Copy, Debug)]
+enum Part {
+    Type,
+    Scope,
+    Subject,
+    Body,
+    Footer,
+}
+
+impl Part {
+    /// Prefixes of the rules which check this part, all other rules are only checked with the complete message
+    fn rule_prefixes(&self) -> &'static [&'static str] {
+        match self {
+            Part::Type => &["type-"],
+            Part::Scope => &["scope-"],
+            Part::Subject => &["subject-", "header-"],
+            Part::Body => &["body-"],
+            Part::Footer => &["footer-", "references-"],
+        }
+    }
+}
+
+/// Answers which were given so far
+#[derive(Clone, Debug, Default)]
+struct Answers {
+    commit_type: String,
+    scope: Option<String>,
+    subject: String,
+    body: Option<String>,
+    breaking_change: Option<String>,
+    references: Vec<String>,
+}
+
+impl Answers {
+    /// Assemble the commit message from the answers
+    fn message(&self) -> String {
+        let mut message = self.commit_type.clone();
+        if let Some(scope) = &self.scope {
+            message += &format!("({})", scope);
+        }
+        if self.breaking_change.is_some() {
+            message.push('!');
+        }
+        message += ": ";
+        message += match self.subject.as_str() {
+            "" => SUBJECT_PLACEHOLDER,
+            subject => subject,
+        };
+
+        if let Some(body) = &self.body {
+            message += &format!("\n\n{}", body);
+        }
+
+        let mut footer: Vec<String> = vec![];
+        if let Some(breaking_change) = &self.breaking_change {
+            footer.push(format!("BREAKING CHANGE: {}", breaking_change));
+        }
+        for reference in &self.references {
+            footer.push(format!("Closes {}", reference));
+        }
+        if !footer.is_empty() {
+            message += &format!("\n\n{}", footer.join("\n"));
+        }
+
+        message
+    }
+}
+
+/// Lint the message with the linter and return the help of the first error of a rule of the given part.
+///
+/// Only errors block the answer, warnings and the rules of the other parts are ignored.
+fn validate(
+    linter: &Linter,
+    context: &LintContext,
+    answers: &Answers,
+    part: Part,
+) -> Result<(), String> {
+    let lint_result = linter.lint_with_context(&answers.message(), context);
+
+    for report in lint_result.errors().into_iter().flatten() {
+        let code = report
+            .code()
+            .map(|code| code.to_string())
+            .unwrap_or_default();
+        let rule = match code.strip_prefix("rule/") {
+            Some(rule) => rule,
+            None if code == "parser" => {
+                return Err(
+                    format!("the {:?} can't be used in a commit message", part).to_lowercase()
+                )
+            }
+            None => continue,
+        };
+
+        if part
+            .rule_prefixes()
+            .iter()
+            .any(|prefix| rule.starts_with(prefix))
+        {
+            return Err(report
+                .help()
+                .map(|help| help.to_string())
+                .unwrap_or_else(|| report.to_string()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Ask for a text and validate the answer live with the rules of the part
+fn ask_text(
+    message: &str,
+    help: &str,
+    linter: &Rc<Linter>,
+    context: &LintContext,
+    answers: &Answers,
+    part: Part,
+    apply: fn(&mut Answers, &str),
+) -> Result<String, InquireError> {
+    let linter = Rc::clone(linter);
+    let context = context.clone();
+    let answers = answers.clone();
+
+    Text::new(message)
+        .with_help_message(help)
+        .with_validator(move |input: &str| {
+            if matches!(part, Part::Type | Part::Subject) && input.trim().is_empty() {
+                return Ok(Validation::Invalid(
+                    format!("the {:?} may not be empty", part)
+                        .to_lowercase()
+                        .into(),
+                ));
+            }
+
+            let mut answers = answers.clone();
+            apply(&mut answers, input);
+            Ok(match validate(&linter, &context, &answers, part) {
+                Ok(()) => Validation::Valid,
+                Err(e) => Validation::Invalid(e.into()),
+            })
+        })
+        .prompt()
+}
+
+/// Let the user select one of the options until the selection passes the rules of the part
+fn ask_select(
+    message: &str,
+    options: Vec<String>,
+    linter: &Linter,
+    context: &LintContext,
+    answers: &mut Answers,
+    part: Part,
+    apply: fn(&mut Answers, &str),
+) -> Result<(), InquireError> {
+    loop {
+        let selected = Select::new(message, options.clone()).prompt()?;
+        apply(answers, &selected);
+        match validate(linter, context, answers, part) {
+            Ok(()) => return Ok(()),
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+}
+
+/// Ask for all parts of the commit message
+fn ask(
+    linter: &Rc<Linter>,
+    context: &LintContext,
+    types: &[String],
+    scopes: &TypedValues,
+) -> Result<Answers, InquireError> {
+    let mut answers = Answers::default();
+
+    let apply_type: fn(&mut Answers, &str) = |answers, input| {
+        answers.commit_type = input.trim().to_string();
+    };
+    if types.is_empty() {
+        let input = ask_text(
+            "Type:",
+            "e.g. feat, fix or chore",
+            linter,
+            context,
+            &answers,
+            Part::Type,
+            apply_type,
+        )?;
+        apply_type(&mut answers, &input);
+    } else {
+        ask_select(
+            "Type:",
+            types.to_vec(),
+            linter,
+            context,
+            &mut answers,
+            Part::Type,
+            apply_type,
+        )?;
+    }
+
+    let apply_scope: fn(&mut Answers, &str) = |answers, input| {
+        answers.scope = match input.trim() {
+            "" | NO_SCOPE => None,
+            scope => Some(scope.to_string()),
+        };
+    };
+    let scopes = scopes.for_type(&answers.commit_type);
+    if scopes.is_empty() {
+        let input = ask_text(
+            "Scope:",
+            "leave empty for no scope",
+            linter,
+            context,
+            &answers,
+            Part::Scope,
+            apply_scope,
+        )?;
+        apply_scope(&mut answers, &input);
+    } else {
+        let options = [NO_SCOPE.to_string()]
+            .into_iter()
+            .chain(scopes.iter().cloned())
+            .collect();
+        ask_select(
+            "Scope:",
+            options,
+            linter,
+            context,
+            &mut answers,
+            Part::Scope,
+            apply_scope,
+        )?;
+    }
+
+    let apply_subject: fn(&mut Answers, &str) = |answers, input| {
+        answers.subject = input.to_string();
+    };
+    let input = ask_text(
+        "Subject:",
+        "short description of the change",
+        linter,
+        context,
+        &answers,
+        Part::Subject,
+        apply_subject,
+    )?;
+    apply_subject(&mut answers, &input);
+
+    let apply_body: fn(&mut Answers, &str) = |answers, input| {
+        answers.body = match input.trim() {
+            "" => None,
+            body => Some(body.to_string()),
+        };
+    };
+    let input = ask_text(
+        "Body:",
+        "longer description of the change, leave empty for no body",
+        linter,
+        context,
+        &answers,
+        Part::Body,
+        apply_body,
+    )?;
+    apply_body(&mut answers, &input);
+
+    if Confirm::new("Are there breaking changes?")
+        .with_default(false)
+        .prompt()?
+    {
+        let apply_breaking_change: fn(&mut Answers, &str) = |answers, input| {
+            answers.breaking_change = Some(input.trim().to_string());
+        };
+        let input = ask_text(
+            "Breaking change:",
+            "describe what breaks and how to migrate",
+            linter,
+            context,
+            &answers,
+            Part::Footer,
+            apply_breaking_change,
+        )?;
+        apply_breaking_change(&mut answers, &input);
+    }
+
+    let apply_references: fn(&mut Answers, &str) = |answers, input| {
+        answers.references = input
+            .split(',')
+            .map(|reference| reference.trim().to_string())
+            .filter(|reference| !reference.is_empty())
+            .collect();
+    };
+    let input = ask_text(
+        "Closed issues:",
+        "comma separated, e.g. #123, PROJ-456, leave empty for none",
+        linter,
+        context,
+        &answers,
+        Part::Footer,
+        apply_references,
+    )?;
+    apply_references(&mut answers, &input);
+
+    Ok(answers)
+}
+
+/// Interactively assemble a commit message which passes all rules
+pub fn run(args: &PromptArgs, cwd: &Path, config_path: &Path) -> ExitCode {
+    let config = match config::load_config(config::find_config_file(config_path).as_deref()) {
+        Ok(config) => config,
+        Err(report) => {
+            eprintln!("{:?}", report);
+            return ExitCode::FAILURE;
+        }
+    };
+    let types = config.allowed_types();
+    let scopes = config.allowed_scopes();
+    let linter = match Linter::from_config(config) {
+        Ok(linter) => Rc::new(linter),
+        Err(report) => {
+            eprintln!("{:?}", report);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // the same context as for linting the commit later, git errors leave it unknown
+    let context = LintContext {
+        author: git::author(cwd).ok(),
+        changed_files: match needs_changed_files(&linter) {
+            true => git::staged_files(cwd).ok(),
+            false => None,
+        },
+    };
+
+    let answers = match ask(&linter, &context, &types, &scopes) {
+        Ok(answers) => answers,
+        Err(InquireError::OperationCanceled | InquireError::OperationInterrupted) => {
+            eprintln!("Aborted");
+            return ExitCode::FAILURE;
+        }
+        Err(e) => {
+            eprintln!("Could not prompt for the commit message: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let message = answers.message();
+
+    // every answer passed the rules of its part, but rules of the whole message can still fail
+    let lint_result = linter.lint_with_context(&message, &context);
+    if lint_result.has_errors() {
+        for report in lint_result.errors().into_iter().flatten() {
+            eprintln!("{:?}", report);
+        }
+        eprintln!("The commit message doesn't pass the rules:\n\n{}", message);
+        return ExitCode::FAILURE;
+    }
+
+    if args.commit {
+        if let Err(report) = git::commit(cwd, &message) {
+            eprintln!("{:?}", report);
+            return ExitCode::FAILURE;
+        }
+    } else {
+        println!("{}", message);
+    }
+
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use commitguard::{config::RulesConfig, parse_commit};
+
+    use super::*;
+
+    #[test]
+    fn test_message() {
+        let mut answers = Answers {
+            commit_type: "feat".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(answers.message(), "feat: subject");
+
+        answers.scope = Some("api".to_string());
+        answers.subject = "add endpoint".to_string();
+        answers.body = Some("some body".to_string());
+        answers.breaking_change = Some("removed old endpoint".to_string());
+        answers.references = vec!["#1".to_string(), "#2".to_string()];
+        assert_eq!(
+            answers.message(),
+            "feat(api)!: add endpoint\n\nsome body\n\nBREAKING CHANGE: removed old endpoint\nCloses #1\nCloses #2"
+        );
+        assert!(parse_commit(&answers.message()).is_ok());
+    }
+
+    #[test]
+    fn test_validate() {
+        let linter = Linter::from_config(
+            RulesConfig::from_toml(
+                r#"
+                [parser]
+                scope-delimiters = ["+"]
+
+                [rules]
+                type-enum = ["error", "always", ["feat", "docs"]]
+                scope-empty = ["warning", "never"]
+                scope-enum = ["error", "always", ["api", "ui"]]
+                subject-full-stop = ["error", "never", "."]
+
+                [rules.when.type.docs]
+                subject-full-stop = ["off"]
+                "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let context = LintContext::default();
+        let validate = |answers: &Answers, part| validate(&linter, &context, answers, part);
+
+        let mut answers = Answers {
+            commit_type: "feature".to_string(),
+            ..Default::default()
+        };
+        assert!(validate(&answers, Part::Type).is_err());
+        // invalid types don't matter while the subject is asked
+        assert!(validate(&answers, Part::Subject).is_ok());
+
+        answers.commit_type = "feat".to_string();
+        assert!(validate(&answers, Part::Type).is_ok());
+        // warnings don't block the answer
+        assert!(validate(&answers, Part::Scope).is_ok());
+
+        // the scopes are split with the delimiters of the parser options
+        answers.scope = Some("api+ui".to_string());
+        assert!(validate(&answers, Part::Scope).is_ok());
+        answers.scope = Some("api,ui".to_string());
+        assert!(validate(&answers, Part::Scope).is_err());
+        answers.scope = None;
+
+        answers.subject = "add feature.".to_string();
+        assert_eq!(
+            validate(&answers, Part::Subject),
+            Err("subject must not end with \".\"".to_string())
+        );
+
+        // the rules of the type are used
+        answers.commit_type = "docs".to_string();
+        assert!(validate(&answers, Part::Subject).is_ok());
+
+        answers.commit_type = "fe at".to_string();
+        assert!(validate(&answers, Part::Type).is_err());
+    }
+}
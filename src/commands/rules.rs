@@ -0,0 +1,31 @@
+use std::{path::Path, process::ExitCode};
+
+use commitguard::{rules::RULES, Linter};
+
+/// Print a table of all rules with their configured severity
+pub fn run(config_path: &Path) -> ExitCode {
+    let linter = match Linter::from_config_file(config_path) {
+        Ok(linter) => linter,
+        Err(report) => {
+            eprintln!("{:?}", report);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let name_width = RULES.iter().map(|meta| meta.name.len()).max().unwrap_or(0);
+    println!("{:name_width$}  {:8}  DESCRIPTION", "RULE", "SEVERITY");
+    for meta in RULES.iter() {
+        let severity = linter
+            .rules()
+            .iter()
+            .find(|rule| rule.name() == meta.name)
+            .map(|rule| rule.severity().to_string())
+            .unwrap_or_default();
+        println!(
+            "{:name_width$}  {:8}  {}",
+            meta.name, severity, meta.description
+        );
+    }
+
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,226 @@
+use std::{
+    collections::BTreeMap,
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+use config::{builder::DefaultState, Config, ConfigBuilder, FileFormat, Value, ValueKind};
+use miette::{miette, Report};
+use serde::Deserialize;
+
+use crate::{
+    branch::BranchOptions,
+    bump::BumpOptions,
+    changelog::ChangelogOptions,
+    parser::ParserOptions,
+    plugins::PluginConfig,
+    rules::{Condition, EnumOpts, Severity, TypedEnumOpts, TypedValues, RULES},
+};
+
+/// Config
+#[derive(Debug, Deserialize)]
+pub struct RulesConfig {
+    /// Options of every rule by its name, which are deserialized by the rule registry
+    pub(crate) rules: BTreeMap<String, Value>,
+    /// Plugins by the name of the rule they provide
+    #[serde(default)]
+    pub(crate) plugins: BTreeMap<String, PluginConfig>,
+    /// Options for parsing the commit messages
+    #[serde(default)]
+    pub(crate) parser: ParserOptions,
+    /// Options for generating the changelog
+    #[serde(default)]
+    pub(crate) changelog: ChangelogOptions,
+    /// Options for the version bump
+    #[serde(default)]
+    pub(crate) bump: BumpOptions,
+    /// Options for linting branch names
+    #[serde(default)]
+    pub(crate) branch: BranchOptions,
+    /// Directory of the config file, plugin paths are relative to it
+    #[serde(skip)]
+    pub(crate) base_dir: Option<PathBuf>,
+    /// Rule options for some commits, which are configured in `[rules.when]`
+    #[serde(skip)]
+    pub(crate) when: RuleOverrides,
+}
+
+/// Rule options which override the `[rules]` table for some commits
+///
+/// ```toml
+/// [rules.when.type.docs]
+/// scope-empty = ["off"]
+/// ```
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct RuleOverrides {
+    /// Options of every rule by its name for commits of a type (`[rules.when.type.<type>]`)
+    #[serde(default, rename = "type")]
+    pub(crate) types: BTreeMap<String, BTreeMap<String, Value>>,
+}
+
+/// Merge the overriding options of a rule into its options: the given elements of the override
+/// replace the ones at the same position (e.g. `["off"]` only changes the severity)
+pub(crate) fn merge_options(options: &Value, overrides: Value) -> Value {
+    match (options.clone().into_array(), overrides.clone().into_array()) {
+        (Ok(mut options), Ok(overrides)) => {
+            for (i, value) in overrides.into_iter().enumerate() {
+                match options.get_mut(i) {
+                    Some(option) => *option = value,
+                    None => options.push(value),
+                }
+            }
+            Value::new(None, ValueKind::Array(options))
+        }
+        _ => overrides,
+    }
+}
+
+impl RulesConfig {
+    /// Types which are allowed by `type-enum` (empty if every type is allowed)
+    pub fn allowed_types(&self) -> Vec<String> {
+        self.allowed_values("type-enum")
+    }
+
+    /// Scopes which are allowed by `scope-enum` (empty for a type if every scope is allowed)
+    pub fn allowed_scopes(&self) -> TypedValues {
+        let opts = self
+            .rules
+            .get("scope-enum")
+            .and_then(|value| value.clone().try_deserialize::<TypedEnumOpts>().ok());
+
+        match opts {
+            Some(TypedEnumOpts(Severity::Error | Severity::Warning, Condition::Always, values)) => {
+                values
+            }
+            _ => TypedValues::default(),
+        }
+    }
+
+    /// Options of the `[changelog]` table
+    pub fn changelog_options(&self) -> &ChangelogOptions {
+        &self.changelog
+    }
+
+    /// Options of the `[bump]` table
+    pub fn bump_options(&self) -> &BumpOptions {
+        &self.bump
+    }
+
+    /// Options of the `[branch]` table
+    pub fn branch_options(&self) -> &BranchOptions {
+        &self.branch
+    }
+
+    /// Values of an enum rule, if it only allows these values
+    fn allowed_values(&self, name: &str) -> Vec<String> {
+        let opts = self
+            .rules
+            .get(name)
+            .and_then(|value| value.clone().try_deserialize::<EnumOpts>().ok());
+
+        match opts {
+            Some(EnumOpts(Severity::Error | Severity::Warning, Condition::Always, values)) => {
+                values
+            }
+            _ => vec![],
+        }
+    }
+}
+
+/// Extensions which are tried if the config file is given without one (same order as the `config` crate uses)
+const CONFIG_EXTENSIONS: [&str; 4] = ["toml", "json", "yaml", "yml"];
+
+/// All paths which are tried for the config path in this order: the path itself and the path with every extension
+pub fn config_file_candidates(config_path: &Path) -> Vec<PathBuf> {
+    let with_extensions = CONFIG_EXTENSIONS.iter().map(|extension| {
+        // append the extension, so that `commitguard.config` becomes `commitguard.config.toml`
+        let mut path = OsString::from(config_path.as_os_str());
+        path.push(".");
+        path.push(extension);
+        PathBuf::from(path)
+    });
+
+    std::iter::once(config_path.to_path_buf())
+        .chain(with_extensions)
+        .collect()
+}
+
+/// Find the config file for the given path, which can be given with or without extension
+pub fn find_config_file(config_path: &Path) -> Option<PathBuf> {
+    config_file_candidates(config_path)
+        .into_iter()
+        .find(|path| path.is_file())
+}
+
+/// Config builder which contains the default options of every rule,
+/// so they are used if the config file doesn't override them
+fn builder_with_defaults() -> ConfigBuilder<DefaultState> {
+    let defaults = RULES
+        .iter()
+        .fold(String::from("[rules]\n"), |mut defaults, meta| {
+            defaults.push_str(&format!("{} = {}\n", meta.name, meta.default));
+            defaults
+        });
+
+    Config::builder().add_source(config::File::from_str(&defaults, FileFormat::Toml))
+}
+
+/// Build the config and turn errors into a report
+fn build(builder: ConfigBuilder<DefaultState>) -> Result<RulesConfig, Report> {
+    builder
+        .build()
+        .and_then(|raw| {
+            let mut config = raw.clone().try_deserialize::<RulesConfig>()?;
+            // `when` is part of the `[rules]` table, but it's not a rule
+            if let Some(when) = config.rules.remove("when") {
+                config.when = when.try_deserialize()?;
+            }
+            // branches allow the types of the `type-enum` rule, unless the `[branch]` table has its own
+            if raw.get::<Value>("branch.type-enum").is_err() {
+                if let Some(type_enum) = config.rules.get("type-enum") {
+                    config.branch.type_enum = type_enum.clone().try_deserialize()?;
+                }
+            }
+            Ok(config)
+        })
+        .map_err(|e| miette!(code = "config", "Invalid config: {}", e))
+}
+
+/// Load the config from the given file and fill up missing rules with the defaults
+pub fn load_config(config_file: Option<&Path>) -> Result<RulesConfig, Report> {
+    let mut builder = builder_with_defaults();
+
+    // Source can be `commitlint.config.toml` or `commitlint.config.json``
+    if let Some(config_file) = config_file {
+        builder = builder.add_source(config::File::from(config_file));
+    }
+
+    let mut config = build(builder)?;
+    config.base_dir = config_file.and_then(Path::parent).map(Path::to_path_buf);
+
+    Ok(config)
+}
+
+impl RulesConfig {
+    /// Load the config from a TOML string and fill up missing rules with the defaults
+    ///
+    /// ```
+    /// use commitguard::config::RulesConfig;
+    ///
+    /// let config = RulesConfig::from_toml(r#"
+    ///     [rules]
+    ///     type-enum = ["error", "always", ["feat", "fix"]]
+    /// "#).unwrap();
+    /// assert_eq!(config.allowed_types(), ["feat", "fix"]);
+    /// ```
+    pub fn from_toml(content: &str) -> Result<Self, Report> {
+        build(builder_with_defaults().add_source(config::File::from_str(content, FileFormat::Toml)))
+    }
+}
+
+impl Default for RulesConfig {
+    /// Config with the default options of every rule
+    fn default() -> Self {
+        build(builder_with_defaults()).expect("the default options of all rules are valid")
+    }
+}
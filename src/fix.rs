@@ -0,0 +1,172 @@
+use crate::{
+    parser::{parse_commit_loose, Commit, ParserOptions},
+    rules::{rules_for_commit, Fix, Rule, Severity, TypeOverrides},
+};
+
+/// Maximum number of fix passes, so that rules with conflicting fixes can't loop forever
+const MAX_PASSES: usize = 10;
+
+/// Apply the fixes of all violated rules until the message doesn't change anymore.
+///
+/// Overlapping fixes are skipped in one pass and applied in the next one after the message was parsed again.
+pub fn fix_commit(
+    message: &str,
+    rules: &[Box<dyn Rule>],
+    overrides: &TypeOverrides,
+    options: &ParserOptions,
+) -> String {
+    let mut message = message.to_string();
+
+    for _ in 0..MAX_PASSES {
+        let fixes: Vec<Fix> = match parse_commit_loose(&message, options) {
+            Ok(commit) => rule_fixes(&commit, rules, overrides)
+                .into_iter()
+                .flat_map(|(_, fixes)| fixes)
+                .collect(),
+            // nothing can be fixed if the message can't be parsed
+            Err(_) => break,
+        };
+
+        let fixed = apply_fixes(&message, fixes);
+        if fixed == message {
+            break;
+        }
+        message = fixed;
+    }
+
+    message
+}
+
+/// Fixes of every violated rule together with the name of the rule
+pub fn rule_fixes(
+    commit: &Commit,
+    rules: &[Box<dyn Rule>],
+    overrides: &TypeOverrides,
+) -> Vec<(&'static str, Vec<Fix>)> {
+    rules_for_commit(commit, rules, overrides)
+        .into_iter()
+        .filter(|rule| rule.severity() != &Severity::Off)
+        .filter(|rule| rule.run(commit).is_some())
+        .filter_map(|rule| rule.fix(commit).map(|fixes| (rule.name(), fixes)))
+        .collect()
+}
+
+/// Apply all fixes which don't overlap with a fix before them
+fn apply_fixes(message: &str, mut fixes: Vec<Fix>) -> String {
+    fixes.sort_by_key(|fix| (fix.start, fix.end));
+
+    let mut fixed = String::with_capacity(message.len());
+    let mut last_end = 0;
+    for fix in fixes {
+        if fix.start < last_end {
+            continue;
+        }
+        fixed.push_str(&message[last_end..fix.start]);
+        fixed.push_str(&fix.replacement);
+        last_end = fix.end;
+    }
+    fixed.push_str(&message[last_end..]);
+
+    fixed
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_commit;
+    use crate::rules::{
+        body_leading_blank::BodyLeadingBlankRule, footer_leading_blank::FooterLeadingBlankRule,
+        header_trim::HeaderTrimRule, subject_full_stop::SubjectFullStopRule,
+        type_case::TypeCaseRule, CaseOpts, Condition, NoOpts, TargetCase, ValueOpts,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_apply_fixes() {
+        let fixes = vec![Fix::delete(10, 11), Fix::insert(0, "a"), Fix::delete(2, 4)];
+        assert_eq!(apply_fixes("0123456789.", fixes), "a01456789");
+    }
+
+    #[test]
+    fn test_apply_overlapping_fixes() {
+        // the second fix overlaps the first one and is skipped
+        let fixes = vec![Fix::delete(0, 4), Fix::delete(2, 6)];
+        assert_eq!(apply_fixes("0123456789", fixes), "456789");
+    }
+
+    #[test]
+    fn test_rule_fixes() {
+        let rules: Vec<Box<dyn Rule>> = vec![
+            Box::new(TypeCaseRule {
+                opts: CaseOpts(Severity::Error, Condition::Always, TargetCase::Lower),
+            }),
+            Box::new(SubjectFullStopRule {
+                opts: ValueOpts(Severity::Error, Condition::Never, ".".to_string()),
+            }),
+        ];
+
+        let commit = parse_commit("FEAT: add feature").unwrap();
+        let fixes = rule_fixes(&commit, &rules, &TypeOverrides::new());
+        assert_eq!(fixes.len(), 1);
+        assert_eq!(fixes[0].0, "type-case");
+        assert_eq!(fixes[0].1[0].replacement, "feat");
+    }
+
+    #[test]
+    fn test_fix_commit() {
+        let rules: Vec<Box<dyn Rule>> = vec![
+            Box::new(TypeCaseRule {
+                opts: CaseOpts(Severity::Error, Condition::Always, TargetCase::Lower),
+            }),
+            Box::new(SubjectFullStopRule {
+                opts: ValueOpts(Severity::Error, Condition::Never, ".".to_string()),
+            }),
+            Box::new(HeaderTrimRule {
+                opts: NoOpts(Severity::Error, Condition::Always),
+            }),
+        ];
+
+        assert_eq!(
+            fix_commit(
+                "FEAT: add feature. \n\nsome body",
+                &rules,
+                &TypeOverrides::new(),
+                &ParserOptions::default()
+            ),
+            "feat: add feature\n\nsome body"
+        );
+        assert_eq!(
+            fix_commit(
+                "not a commit",
+                &rules,
+                &TypeOverrides::new(),
+                &ParserOptions::default()
+            ),
+            "not a commit"
+        );
+    }
+
+    #[test]
+    fn test_fix_blank_lines() {
+        let rules: Vec<Box<dyn Rule>> = vec![
+            Box::new(BodyLeadingBlankRule {
+                opts: NoOpts(Severity::Warning, Condition::Always),
+            }),
+            Box::new(FooterLeadingBlankRule {
+                opts: NoOpts(Severity::Warning, Condition::Always),
+            }),
+        ];
+
+        // the message can't be linted without the blank line in front of the body, but it can be fixed
+        assert!(parse_commit("feat: add feature\nsome body\nCloses #1").is_err());
+        assert_eq!(
+            fix_commit(
+                "feat: add feature\nsome body\nCloses #1",
+                &rules,
+                &TypeOverrides::new(),
+                &ParserOptions::default()
+            ),
+            "feat: add feature\n\nsome body\n\nCloses #1"
+        );
+    }
+}
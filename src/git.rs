@@ -0,0 +1,180 @@
+use std::{path::Path, process::Command};
+
+use miette::{miette, Report};
+
+/// A commit read from the git history
+#[derive(Debug)]
+pub struct GitCommit {
+    /// Full hash of the commit
+    pub hash: String,
+    /// Identity of the author (e.g. `Jane Doe <jane@example.com>`)
+    pub author: String,
+    /// Raw commit message
+    pub message: String,
+}
+
+impl GitCommit {
+    /// Abbreviated hash of the commit
+    pub fn short_hash(&self) -> &str {
+        &self.hash[..self.hash.len().min(7)]
+    }
+}
+
+/// Run git with the given arguments in `cwd` and return its stdout
+fn git(cwd: &Path, args: &[&str]) -> Result<String, Report> {
+    let output = Command::new("git")
+        .current_dir(cwd)
+        .args(args)
+        .output()
+        .map_err(|e| miette!(code = "git", "Could not run git: {}", e))?;
+
+    if !output.status.success() {
+        return Err(miette!(
+            code = "git",
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Read all commits reachable from `to` but not from `from` (oldest first)
+pub fn commits_in_range(cwd: &Path, from: &str, to: &str) -> Result<Vec<GitCommit>, Report> {
+    log(cwd, &format!("{}..{}", from, to))
+}
+
+/// Read all commits of the given revision or range (oldest first)
+pub fn log(cwd: &Path, revisions: &str) -> Result<Vec<GitCommit>, Report> {
+    // hash, author and message are separated by NUL bytes and every commit ends with a record separator
+    let log = git(
+        cwd,
+        &[
+            "log",
+            "--reverse",
+            "--format=%H%x00%an <%ae>%x00%B%x1e",
+            revisions,
+        ],
+    )?;
+
+    Ok(log
+        .split('\u{1e}')
+        .filter_map(|entry| {
+            let mut fields = entry.trim_start_matches('\n').splitn(3, '\0');
+            let (hash, author, message) = (fields.next()?, fields.next()?, fields.next()?);
+            Some(GitCommit {
+                hash: hash.to_string(),
+                author: author.to_string(),
+                message: message.trim_end().to_string(),
+            })
+        })
+        .collect())
+}
+
+/// Identity of the author of new commits (e.g. `Jane Doe <jane@example.com>`)
+pub fn author(cwd: &Path) -> Result<String, Report> {
+    let ident = git(cwd, &["var", "GIT_AUTHOR_IDENT"])?;
+
+    // the identity is followed by the timestamp and the timezone
+    Ok(ident
+        .trim()
+        .rsplitn(3, ' ')
+        .nth(2)
+        .unwrap_or_default()
+        .to_string())
+}
+
+/// Name of the current branch (`HEAD` if it is detached)
+pub fn current_branch(cwd: &Path) -> Result<String, Report> {
+    Ok(git(cwd, &["rev-parse", "--abbrev-ref", "HEAD"])?
+        .trim()
+        .to_string())
+}
+
+/// Names of all tags which are reachable from the revision
+pub fn tags(cwd: &Path, revision: &str) -> Result<Vec<String>, Report> {
+    Ok(git(cwd, &["tag", "--list", "--merged", revision])?
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
+
+/// Date of the commit of the revision (e.g. `2024-01-31`)
+pub fn commit_date(cwd: &Path, revision: &str) -> Result<String, Report> {
+    Ok(git(cwd, &["log", "-1", "--format=%cs", revision])?
+        .trim()
+        .to_string())
+}
+
+/// Paths of the files which the commit changes, relative to the root of the repository
+pub fn changed_files(cwd: &Path, revision: &str) -> Result<Vec<String>, Report> {
+    Ok(git(
+        cwd,
+        // `--root` lists the files of a commit without parent, too
+        &[
+            "diff-tree",
+            "--root",
+            "--no-commit-id",
+            "--name-only",
+            "-r",
+            revision,
+        ],
+    )?
+    .lines()
+    .map(str::to_string)
+    .collect())
+}
+
+/// Paths of the staged files, relative to the root of the repository
+pub fn staged_files(cwd: &Path) -> Result<Vec<String>, Report> {
+    Ok(git(cwd, &["diff", "--cached", "--name-only"])?
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
+
+/// Commit the staged changes with the given message
+pub fn commit(cwd: &Path, message: &str) -> Result<(), Report> {
+    git(cwd, &["commit", "--message", message])?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env::temp_dir, fs};
+
+    use super::*;
+
+    #[test]
+    fn test_changed_files_of_root_commit() {
+        let dir = temp_dir().join(format!("commitguard-git-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("README.md"), "# readme").unwrap();
+        fs::write(dir.join("src/lib.rs"), "").unwrap();
+
+        git(&dir, &["init", "--quiet"]).unwrap();
+        git(&dir, &["add", "."]).unwrap();
+        git(
+            &dir,
+            &[
+                "-c",
+                "user.name=Jane Doe",
+                "-c",
+                "user.email=jane@example.com",
+                "-c",
+                "commit.gpgsign=false",
+                "commit",
+                "--quiet",
+                "--message",
+                "feat: initial commit",
+            ],
+        )
+        .unwrap();
+        let files = changed_files(&dir, "HEAD");
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(files.unwrap(), ["README.md", "src/lib.rs"]);
+    }
+}
@@ -0,0 +1,33 @@
+//! A commitlint implementation in Rust.
+//!
+//! Commit messages are parsed into a [`Commit`] and checked by a [`Linter`],
+//! which runs all configured [rules](rules) and collects their reports in a [`LintResult`].
+//!
+//! ```
+//! use commitguard::{config::RulesConfig, parse_commit, Linter};
+//!
+//! let commit = parse_commit("feat(foo): add new feature").unwrap();
+//! assert_eq!(commit.commit_type.to_string(), "feat");
+//!
+//! let linter = Linter::from_config(RulesConfig::default()).unwrap();
+//! let result = linter.lint("feat(foo): add new feature");
+//! assert!(!result.has_errors());
+//! ```
+
+pub mod branch;
+pub mod bump;
+pub mod changelog;
+pub mod config;
+mod fix;
+mod linter;
+pub mod parser;
+pub mod plugins;
+mod position;
+mod registry;
+pub mod rules;
+
+pub use linter::Linter;
+pub use parser::{parse_commit, Commit, CommitSpan, Reference};
+pub use position::{LineIndex, Position};
+pub use registry::RuleRegistry;
+pub use rules::{LintContext, LintResult, Rule};
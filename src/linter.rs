@@ -0,0 +1,267 @@
+use std::path::Path;
+
+use miette::Report;
+
+use crate::{
+    config::{self, RulesConfig},
+    fix,
+    parser::{parse_commit_loose, parse_commit_with, Commit, ParserOptions},
+    registry::RuleRegistry,
+    rules::{self, Fix, LintContext, LintResult, Rule, Severity, TypeOverrides},
+};
+
+/// Lints commit messages with a set of rules
+///
+/// ```
+/// use commitguard::{config::RulesConfig, Linter};
+///
+/// let config = RulesConfig::from_toml(r#"
+///     [rules]
+///     scope-empty = ["off", "never"]
+///     type-enum = ["error", "always", ["feat", "fix"]]
+/// "#).unwrap();
+/// let linter = Linter::from_config(config).unwrap();
+///
+/// assert!(!linter.lint("feat: add new feature").has_errors());
+/// assert!(linter.lint("feature: add new feature").has_errors());
+/// ```
+pub struct Linter {
+    rules: Vec<Box<dyn Rule>>,
+    /// Rules which replace the rules of the same name for commits of a type
+    type_overrides: TypeOverrides,
+    parser_options: ParserOptions,
+}
+
+impl Linter {
+    /// Create a linter with all built-in rules configured by the config
+    pub fn from_config(config: RulesConfig) -> Result<Self, Report> {
+        Self::from_config_with_registry(config, &RuleRegistry::default())
+    }
+
+    /// Create a linter with the rules of the registry which are configured by the config.
+    /// Fails if the config contains an unknown rule or invalid options.
+    ///
+    /// Rules can be configured differently for commits of a type in `[rules.when.type.<type>]`:
+    ///
+    /// ```
+    /// use commitguard::{config::RulesConfig, Linter};
+    ///
+    /// let config = RulesConfig::from_toml(r#"
+    ///     [rules.when.type.docs]
+    ///     scope-empty = ["off"]
+    /// "#).unwrap();
+    /// let linter = Linter::from_config(config).unwrap();
+    ///
+    /// assert!(!linter.lint("docs: update readme").has_errors());
+    /// assert!(linter.lint("feat: add new feature").has_errors());
+    /// ```
+    pub fn from_config_with_registry(
+        mut config: RulesConfig,
+        registry: &RuleRegistry,
+    ) -> Result<Self, Report> {
+        let parser_options = std::mem::take(&mut config.parser);
+        let type_overrides = registry.create_type_overrides(&config)?;
+
+        Ok(Linter {
+            rules: registry.create_rules(config)?,
+            type_overrides,
+            parser_options,
+        })
+    }
+
+    /// Create a linter from a config file, which can be given with or without extension
+    /// (e.g. `commitguard.config` finds `commitguard.config.toml`).
+    /// The defaults of all rules are used if there is no config file.
+    pub fn from_config_file(config_path: &Path) -> Result<Self, Report> {
+        let config = config::load_config(config::find_config_file(config_path).as_deref())?;
+
+        Self::from_config(config)
+    }
+
+    /// Add a custom rule, which is run after the configured rules
+    ///
+    /// ```
+    /// use commitguard::{rules::Severity, Commit, Linter, Rule};
+    ///
+    /// struct NoWipRule;
+    ///
+    /// impl Rule for NoWipRule {
+    ///     fn name(&self) -> &'static str {
+    ///         "no-wip"
+    ///     }
+    ///
+    ///     fn severity(&self) -> &Severity {
+    ///         &Severity::Error
+    ///     }
+    ///
+    ///     fn run(&self, commit: &Commit) -> Option<miette::Report> {
+    ///         commit
+    ///             .subject
+    ///             .to_string()
+    ///             .contains("WIP")
+    ///             .then(|| miette::miette!(severity = miette::Severity::Error, "Work in progress"))
+    ///     }
+    /// }
+    ///
+    /// let linter = Linter::from_config(Default::default())
+    ///     .unwrap()
+    ///     .with_rule(Box::new(NoWipRule));
+    /// assert!(linter.lint("fix(foo): WIP").has_errors());
+    /// ```
+    pub fn with_rule(mut self, rule: Box<dyn Rule>) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// All rules of the linter (without the overrides for commit types)
+    pub fn rules(&self) -> &[Box<dyn Rule>] {
+        &self.rules
+    }
+
+    /// Rules which override the rules for commits of a type (`[rules.when.type.<type>]`)
+    pub fn type_overrides(&self) -> &TypeOverrides {
+        &self.type_overrides
+    }
+
+    /// Parse the commit message with the configured parser options.
+    /// A body without a blank line in front of it is only parsed if `body-leading-blank` checks it.
+    ///
+    /// ```
+    /// use commitguard::{config::RulesConfig, Linter};
+    ///
+    /// let config = RulesConfig::from_toml(r#"
+    ///     [parser]
+    ///     issue-prefixes = ["PROJ-"]
+    /// "#).unwrap();
+    /// let linter = Linter::from_config(config).unwrap();
+    ///
+    /// let commit = linter.parse("fix: crash on start\n\nCloses PROJ-123").unwrap();
+    /// assert_eq!(commit.references[0].issue.to_string(), "123");
+    /// ```
+    pub fn parse<'a>(&self, message: &'a str) -> Result<Commit<'a>, Report> {
+        parse_commit_with(message, &self.parser_options).or_else(|report| match parse_commit_loose(
+            message,
+            &self.parser_options,
+        ) {
+            Ok(commit) if self.checks_body_leading_blank(&commit) => Ok(commit),
+            _ => Err(report),
+        })
+    }
+
+    /// Check if `body-leading-blank` is turned on for the commit
+    fn checks_body_leading_blank(&self, commit: &Commit) -> bool {
+        rules::rules_for_commit(commit, &self.rules, &self.type_overrides)
+            .iter()
+            .any(|rule| {
+                rule.name() == rules::body_leading_blank::META.name
+                    && rule.severity() != &Severity::Off
+            })
+    }
+
+    /// Parse the commit message and run all rules against it.
+    /// A message which can't be parsed results in a single error.
+    pub fn lint(&self, message: &str) -> LintResult {
+        self.lint_with_context(message, &LintContext::default())
+    }
+
+    /// Like `lint`, with information about the commit which is not part of its message
+    ///
+    /// ```
+    /// use commitguard::{config::RulesConfig, LintContext, Linter};
+    ///
+    /// let config = RulesConfig::from_toml(r#"
+    ///     [rules]
+    ///     signed-off-by = ["error", "always", "Signed-off-by:"]
+    /// "#).unwrap();
+    /// let linter = Linter::from_config(config).unwrap();
+    /// let context = LintContext {
+    ///     author: Some("Jane Doe <jane@example.com>".to_string()),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let message = "feat(foo): add feature\n\nSigned-off-by: Jane Doe <jane@example.com>";
+    /// assert!(!linter.lint_with_context(message, &context).has_errors());
+    /// let message = "feat(foo): add feature\n\nSigned-off-by: John Doe <john@example.com>";
+    /// assert!(linter.lint_with_context(message, &context).has_errors());
+    /// ```
+    pub fn lint_with_context(&self, message: &str, context: &LintContext) -> LintResult {
+        match self.parse(message) {
+            Ok(commit) => rules::lint(&commit, &self.rules, &self.type_overrides, context),
+            Err(report) => LintResult::from_parse_error(report),
+        }
+    }
+
+    /// Lint a single header (e.g. the title of a pull request which becomes the header of the squashed commit),
+    /// only the rules which check the header are run
+    ///
+    /// ```
+    /// use commitguard::{config::RulesConfig, Linter};
+    ///
+    /// let config = RulesConfig::from_toml(r#"
+    ///     [rules]
+    ///     trailer-exists = ["error", "always", "Signed-off-by:"]
+    ///     type-case = ["error", "always", "lower-case"]
+    /// "#).unwrap();
+    /// let linter = Linter::from_config(config).unwrap();
+    ///
+    /// assert!(linter.lint("feat(foo): add feature").has_errors());
+    /// assert!(!linter.lint_title("feat(foo): add feature").has_errors());
+    /// assert!(linter.lint_title("FEAT(foo): add feature").has_errors());
+    /// ```
+    pub fn lint_title(&self, title: &str) -> LintResult {
+        match self.parse(title) {
+            Ok(commit) => rules::lint_rules(
+                &commit,
+                rules::rules_for_commit(&commit, &self.rules, &self.type_overrides)
+                    .into_iter()
+                    .filter(|rule| rules::is_header_rule(rule.name())),
+                &LintContext::default(),
+            ),
+            Err(report) => LintResult::from_parse_error(report),
+        }
+    }
+
+    /// Automatic fixes of the violated rules, grouped by the name of the rule.
+    /// A message which can't be parsed has no fixes, unless only the blank line in front of the body is missing.
+    ///
+    /// ```
+    /// use commitguard::{config::RulesConfig, Linter};
+    ///
+    /// let config = RulesConfig::from_toml(r#"
+    ///     [rules]
+    ///     type-case = ["error", "always", "lower-case"]
+    /// "#).unwrap();
+    /// let linter = Linter::from_config(config).unwrap();
+    /// let fixes = linter.fixes("FEAT(foo): add feature");
+    /// assert_eq!(fixes[0].0, "type-case");
+    /// assert_eq!(fixes[0].1[0].replacement, "feat");
+    /// ```
+    pub fn fixes(&self, message: &str) -> Vec<(&'static str, Vec<Fix>)> {
+        match parse_commit_loose(message, &self.parser_options) {
+            Ok(commit) => fix::rule_fixes(&commit, &self.rules, &self.type_overrides),
+            Err(_) => vec![],
+        }
+    }
+
+    /// Apply the automatic fixes of all violated rules and return the fixed message
+    ///
+    /// ```
+    /// use commitguard::{config::RulesConfig, Linter};
+    ///
+    /// let config = RulesConfig::from_toml(r#"
+    ///     [rules]
+    ///     type-case = ["error", "always", "lower-case"]
+    ///     subject-full-stop = ["error", "never", "."]
+    /// "#).unwrap();
+    /// let linter = Linter::from_config(config).unwrap();
+    /// assert_eq!(linter.fix("FEAT(foo): add feature."), "feat(foo): add feature");
+    /// ```
+    pub fn fix(&self, message: &str) -> String {
+        fix::fix_commit(
+            message,
+            &self.rules,
+            &self.type_overrides,
+            &self.parser_options,
+        )
+    }
+}
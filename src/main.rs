@@ -1,73 +1,76 @@
-mod parser;
-mod rules;
+mod commands;
+mod git;
 
-use std::{
-    env::current_dir,
-    io::{stdin, Read},
-    path::PathBuf,
-    process::ExitCode,
-};
+use std::{env::current_dir, path::PathBuf, process::ExitCode};
 
 use clap::Parser;
-use miette::GraphicalReportHandler;
-use parser::parse_commit;
+use commands::{
+    bump::BumpArgs, changelog::ChangelogArgs, init::InitArgs, lint::LintArgs,
+    lint_branch::LintBranchArgs, lint_title::LintTitleArgs, prompt::PromptArgs,
+};
 
 /// Commit lint
 #[derive(clap::Parser, Debug)]
-#[command(version, about, long_about = None)]
+#[command(version, about, long_about = None, args_conflicts_with_subcommands = true)]
 struct Cli {
     /// Path to the config file
-    #[arg(short, long, default_value = "commitguard.config")]
+    #[arg(short, long, default_value = "commitguard.config", global = true)]
     config_name: String,
 
     /// Current working directory
-    #[arg(long, default_value = current_dir().unwrap_or_else(|_e| PathBuf::from("/")).into_os_string())]
+    #[arg(long, default_value = current_dir().unwrap_or_else(|_e| PathBuf::from("/")).into_os_string(), global = true)]
     cwd: PathBuf,
-}
-
-fn main() -> ExitCode {
-    let args = Cli::parse();
-
-    // read commit from stdin
-    let mut buffer = String::new();
-    stdin().read_to_string(&mut buffer).unwrap_or(0);
-    let commit = parse_commit(&buffer);
-    // println!("{:#?}", commit);
-
-    let config_path = args.cwd.join(args.config_name);
-    let lint_result = rules::run(&commit, config_path);
-
-    let report_handler = GraphicalReportHandler::new();
 
-    if lint_result.has_warnings() {
-        let mut out = String::new();
-        lint_result.warnings().unwrap().iter().for_each(|report| {
-            out.push('\n');
-            let _ = report_handler.render_report(&mut out, report.as_ref());
-        });
+    #[command(flatten)]
+    lint: LintArgs,
 
-        println!("{}", out);
-    }
-
-    if lint_result.has_errors() {
-        let mut out = String::new();
-        lint_result.errors().unwrap().iter().for_each(|report| {
-            out.push('\n');
-            let _ = report_handler.render_report(&mut out, report.as_ref());
-        });
+    #[command(subcommand)]
+    command: Option<Command>,
+}
 
-        println!("{}", out);
-    }
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// List all rules with their configured severity
+    Rules,
+    /// Explain what a rule checks and how it can be configured
+    Explain {
+        /// Name of the rule (e.g. `scope-enum`)
+        rule: String,
+    },
+    /// Create a config file from a preset
+    Init(InitArgs),
+    /// Interactively write a commit message which follows the configured rules
+    Prompt(PromptArgs),
+    /// Generate the changelog of a release from the commit history
+    Changelog(ChangelogArgs),
+    /// Print the next version, which the commits since the latest version tag require
+    Bump(BumpArgs),
+    /// Lint the title of a pull request with the rules which check the header
+    LintTitle(LintTitleArgs),
+    /// Lint the name of a branch with the options of the `[branch]` table
+    LintBranch(LintBranchArgs),
+    /// Start a language server on stdio, which lints commit messages in the editor
+    Lsp,
+}
 
-    println!(
-        "There are {} warnings and {} errors",
-        lint_result.warnings_len(),
-        lint_result.errors_len()
-    );
+fn main() -> ExitCode {
+    let args = Cli::parse();
+    let config_path = args.cwd.join(&args.config_name);
 
-    if lint_result.has_errors() {
-        return ExitCode::FAILURE;
+    match &args.command {
+        None => commands::lint::run(&args.lint, &args.cwd, &config_path),
+        Some(Command::Rules) => commands::rules::run(&config_path),
+        Some(Command::Explain { rule }) => commands::explain::run(rule, &config_path),
+        Some(Command::Init(init)) => commands::init::run(init, &args.cwd, &config_path),
+        Some(Command::Prompt(prompt)) => commands::prompt::run(prompt, &args.cwd, &config_path),
+        Some(Command::Changelog(changelog)) => {
+            commands::changelog::run(changelog, &args.cwd, &config_path)
+        }
+        Some(Command::Bump(bump)) => commands::bump::run(bump, &args.cwd, &config_path),
+        Some(Command::LintTitle(lint_title)) => commands::lint_title::run(lint_title, &config_path),
+        Some(Command::LintBranch(lint_branch)) => {
+            commands::lint_branch::run(lint_branch, &args.cwd, &config_path)
+        }
+        Some(Command::Lsp) => commands::lsp::run(&config_path),
     }
-
-    ExitCode::SUCCESS
 }
@@ -1,10 +1,20 @@
-use std::fmt;
+use std::{fmt, sync::OnceLock};
 
-use serde::Serialize;
+use miette::{miette, LabeledSpan, Report};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
 
-use pest::{Parser, Span};
+use pest::{
+    error::{Error, InputLocation},
+    Parser, Span,
+};
 use pest_derive::Parser;
 
+use crate::position::LineIndex;
+
+/// Tokens of the footer which describe a breaking change
+const BREAKING_TOKENS: [&str; 2] = ["BREAKING CHANGE:", "BREAKING-CHANGE:"];
+
 #[derive(Parser)]
 #[grammar = "commit.pest"]
 struct CommitParser;
@@ -37,6 +47,11 @@ impl<'a> CommitSpan<'a> {
     pub fn end(&self) -> usize {
         self.end
     }
+
+    /// The text of the span
+    pub fn as_str(&self) -> &'a str {
+        self.input
+    }
 }
 
 impl fmt::Display for CommitSpan<'_> {
@@ -45,6 +60,238 @@ impl fmt::Display for CommitSpan<'_> {
     }
 }
 
+/// A reference to an issue (e.g. `Closes #123`)
+#[derive(Debug, Serialize)]
+pub struct Reference<'a> {
+    /// The action keyword in front of the issue (e.g. `Closes`)
+    pub action: Option<CommitSpan<'a>>,
+    /// The issue prefix (e.g. `#` or `PROJ-`)
+    pub prefix: CommitSpan<'a>,
+    /// The issue id without the prefix (e.g. `123`)
+    pub issue: CommitSpan<'a>,
+    /// The complete reference including the action
+    pub span: CommitSpan<'a>,
+}
+
+/// Characters which are allowed in a part of the header, as a regex character class (e.g. `[\p{L}\p{N}_-]`)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "String")]
+pub struct CharClass {
+    class: String,
+    regex: Regex,
+}
+
+impl CharClass {
+    pub fn new(class: &str) -> Result<Self, regex::Error> {
+        Ok(CharClass {
+            class: class.to_string(),
+            regex: Regex::new(&format!("^(?:{})$", class))?,
+        })
+    }
+
+    /// Check if the character belongs to the class
+    pub fn matches(&self, c: char) -> bool {
+        self.regex.is_match(c.encode_utf8(&mut [0; 4]))
+    }
+}
+
+impl TryFrom<String> for CharClass {
+    type Error = regex::Error;
+
+    fn try_from(class: String) -> Result<Self, Self::Error> {
+        Self::new(&class)
+    }
+}
+
+impl fmt::Display for CharClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.class)
+    }
+}
+
+/// Built-in formats of the header, configured with `header-format`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HeaderFormat {
+    /// `type(scope)!: subject` (Conventional Commits)
+    #[default]
+    Conventional,
+    /// `type(scope): subject` with the types of the Angular convention and a single space after the colon
+    Angular,
+    /// `[type] subject`
+    Bracket,
+    /// `TYPE-123: subject`, the project key is the type and the ticket number is the scope
+    Ticket,
+}
+
+impl HeaderFormat {
+    /// Regex which parses the header, the conventional format is parsed by the grammar instead
+    fn regex(&self) -> Option<&'static Regex> {
+        static ANGULAR: OnceLock<Regex> = OnceLock::new();
+        static BRACKET: OnceLock<Regex> = OnceLock::new();
+        static TICKET: OnceLock<Regex> = OnceLock::new();
+
+        let (regex, pattern) = match self {
+            HeaderFormat::Conventional => return None,
+            HeaderFormat::Angular => (
+                &ANGULAR,
+                r"^(?P<type>build|ci|docs|feat|fix|perf|refactor|test)(?:\((?P<scope>[^()]+)\))?: (?P<subject>\S.*)$",
+            ),
+            HeaderFormat::Bracket => (&BRACKET, r"^\[(?P<type>[^\[\]\s]+)\]\s+(?P<subject>\S.*)$"),
+            HeaderFormat::Ticket => (
+                &TICKET,
+                r"^(?P<type>[A-Z][A-Z0-9]*)-(?P<scope>[0-9]+):\s+(?P<subject>\S.*)$",
+            ),
+        };
+
+        Some(regex.get_or_init(|| Regex::new(pattern).expect("built-in header formats are valid")))
+    }
+
+    /// How a header in this format looks like
+    fn example(&self) -> &'static str {
+        match self {
+            HeaderFormat::Conventional => "type(scope): subject",
+            HeaderFormat::Angular => "type(scope): subject",
+            HeaderFormat::Bracket => "[type] subject",
+            HeaderFormat::Ticket => "TYPE-123: subject",
+        }
+    }
+}
+
+/// Regex for the header with the named groups `type` and `subject` and optionally `scope`, `breaking` and `emoji`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "String")]
+pub struct HeaderPattern(Regex);
+
+impl TryFrom<String> for HeaderPattern {
+    type Error = String;
+
+    fn try_from(pattern: String) -> Result<Self, Self::Error> {
+        let regex = Regex::new(&pattern).map_err(|e| e.to_string())?;
+        let names: Vec<_> = regex.capture_names().flatten().collect();
+        if !names.contains(&"type") || !names.contains(&"subject") {
+            return Err(String::from(
+                "the header pattern needs the named groups `type` and `subject`",
+            ));
+        }
+
+        Ok(HeaderPattern(regex))
+    }
+}
+
+/// Options for parsing the commit message, configured in the `[parser]` table
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct ParserOptions {
+    /// Prefixes of issue references (e.g. `#` for `#123` or `PROJ-` for `PROJ-123`)
+    pub issue_prefixes: Vec<String>,
+    /// Keywords in front of a reference which act on the issue (matched case-insensitive, e.g. `closes`)
+    pub reference_actions: Vec<String>,
+    /// Delimiters between multiple scopes (e.g. `,` for `feat(api,ui): ...`)
+    pub scope_delimiters: Vec<String>,
+    /// Characters which are allowed in the type (letters and digits of any language, `_` and `-` by default)
+    pub type_chars: CharClass,
+    /// Characters which are allowed in a scope (letters and digits of any language, `@`, `.`, `/`, `_` and `-` by default)
+    pub scope_chars: CharClass,
+    /// Built-in format of the header
+    pub header_format: HeaderFormat,
+    /// Custom regex for the header, which is used instead of the `header_format`
+    pub header_pattern: Option<HeaderPattern>,
+    #[serde(skip)]
+    references_regex: OnceLock<Regex>,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        ParserOptions {
+            issue_prefixes: vec![String::from("#")],
+            reference_actions: [
+                "close", "closes", "closed", "fix", "fixes", "fixed", "resolve", "resolves",
+                "resolved",
+            ]
+            .map(String::from)
+            .to_vec(),
+            scope_delimiters: vec![String::from(",")],
+            type_chars: CharClass::new(r"[\p{L}\p{M}\p{N}_-]").expect("the default class is valid"),
+            scope_chars: CharClass::new(r"[\p{L}\p{M}\p{N}@./_-]")
+                .expect("the default class is valid"),
+            header_format: HeaderFormat::default(),
+            header_pattern: None,
+            references_regex: OnceLock::new(),
+        }
+    }
+}
+
+impl ParserOptions {
+    /// Regex which parses the header and how the header looks like,
+    /// `None` for conventional headers which are parsed by the grammar
+    fn header_regex(&self) -> Option<(&Regex, &str)> {
+        match &self.header_pattern {
+            Some(HeaderPattern(regex)) => Some((regex, regex.as_str())),
+            None => self
+                .header_format
+                .regex()
+                .map(|regex| (regex, self.header_format.example())),
+        }
+    }
+
+    /// Regex which finds the references, it is only compiled once
+    fn references_regex(&self) -> &Regex {
+        self.references_regex.get_or_init(|| {
+            // longer alternatives first, so that `GH-` is preferred over `G`
+            let alternatives = |values: &[String]| {
+                let mut values: Vec<String> =
+                    values.iter().map(|value| regex::escape(value)).collect();
+                values.sort_by_key(|value| std::cmp::Reverse(value.len()));
+                values.join("|")
+            };
+            let action = match self.reference_actions.is_empty() {
+                true => String::new(),
+                false => format!(
+                    r"(?:\b(?P<action>(?i:{})):?\s+)?",
+                    alternatives(&self.reference_actions)
+                ),
+            };
+
+            Regex::new(&format!(
+                r"{}(?P<prefix>{})(?P<issue>[\w-]*\d+)",
+                action,
+                alternatives(&self.issue_prefixes)
+            ))
+            .expect("escaped prefixes and actions are a valid regex")
+        })
+    }
+}
+
+/// Find all issue references in the commit message
+fn parse_references<'a>(commit_msg: &'a str, options: &ParserOptions) -> Vec<Reference<'a>> {
+    if options.issue_prefixes.is_empty() {
+        return vec![];
+    }
+
+    let span =
+        |found: regex::Match<'a>| CommitSpan::new(found.as_str(), found.start(), found.end());
+
+    options
+        .references_regex()
+        .captures_iter(commit_msg)
+        // references have to start at a word boundary (e.g. not `abc#123`)
+        .filter(|captures| {
+            let start = captures.get(0).unwrap().start();
+            !commit_msg[..start]
+                .chars()
+                .next_back()
+                .is_some_and(char::is_alphanumeric)
+        })
+        .map(|captures| Reference {
+            action: captures.name("action").map(span),
+            prefix: span(captures.name("prefix").unwrap()),
+            issue: span(captures.name("issue").unwrap()),
+            span: span(captures.get(0).unwrap()),
+        })
+        .collect()
+}
+
 #[derive(Debug, Serialize)]
 pub struct Commit<'a> {
     /// The complete header of the commit message including the type, scope and subject
@@ -53,12 +300,20 @@ pub struct Commit<'a> {
     pub body: Option<CommitSpan<'a>>,
     /// The footer of the commit message
     pub footer: Option<CommitSpan<'a>>,
+    /// The emoji in front of the type (e.g. `✨` or `:sparkles:`)
+    pub emoji: Option<CommitSpan<'a>>,
     /// The type of the commit message (e.g. feat, fix, chore, ...)
     pub commit_type: CommitSpan<'a>,
     /// The scope of the commit message (e.g. backend, frontend, ...)
     pub scope: Option<CommitSpan<'a>>,
+    /// The individual scopes, split at the configured delimiters (e.g. `api` and `ui` for `api,ui`)
+    pub scopes: Vec<CommitSpan<'a>>,
+    /// The `!` in front of the colon which marks a breaking change
+    pub breaking: Option<CommitSpan<'a>>,
     /// The subject of the commit message
     pub subject: CommitSpan<'a>,
+    /// All issue references in the commit message (e.g. `Closes #123`)
+    pub references: Vec<Reference<'a>>,
     /// The raw commit message
     pub raw: String,
 }
@@ -69,12 +324,96 @@ impl Commit<'_> {
             header: CommitSpan::default(),
             body: None,
             footer: None,
+            emoji: None,
             commit_type: CommitSpan::default(),
             scope: None,
+            scopes: vec![],
+            breaking: None,
             subject: CommitSpan::default(),
+            references: vec![],
             raw: String::from(""),
         }
     }
+
+    /// Index to convert the byte offsets of the spans into line and column positions
+    pub fn line_index(&self) -> LineIndex<'_> {
+        LineIndex::new(&self.raw)
+    }
+
+    /// Description of the breaking change in a `BREAKING CHANGE:` footer,
+    /// or the subject if the header is marked with `!`
+    pub fn breaking_change(&self) -> Option<&str> {
+        let description = self.raw.lines().skip(1).find_map(|line| {
+            BREAKING_TOKENS
+                .iter()
+                .find_map(|token| line.strip_prefix(token))
+                .map(str::trim)
+        });
+
+        match (description, &self.breaking) {
+            (Some(description), _) => Some(description),
+            (None, Some(_)) => Some(self.subject.as_str()),
+            (None, None) => None,
+        }
+    }
+
+    /// Lines of the last paragraph which look like git trailers (e.g. `Signed-off-by: Jane Doe <jane@example.com>`).
+    /// A message without body or footer has no trailers.
+    pub fn trailers(&self) -> Vec<CommitSpan<'_>> {
+        self.last_paragraph_lines()
+            .into_iter()
+            .filter(|line| is_trailer(line.as_str()))
+            .collect()
+    }
+
+    /// Lines of the last paragraph without their line breaks, where trailers are placed.
+    /// A message without body or footer has no last paragraph.
+    pub fn last_paragraph_lines(&self) -> Vec<CommitSpan<'_>> {
+        let message = self.raw.trim_end();
+        // the blank line in front of the last paragraph, which can also end with `\r\n`
+        let Some(paragraph_start) = ["\n\n", "\n\r\n"]
+            .iter()
+            .filter_map(|blank_line| message.rfind(blank_line).map(|pos| pos + blank_line.len()))
+            .max()
+        else {
+            return vec![];
+        };
+
+        let mut start = paragraph_start;
+        message[paragraph_start..]
+            .split('\n')
+            .map(|line| {
+                let span = CommitSpan::new(line.trim_end(), start, start + line.trim_end().len());
+                start += line.len() + 1;
+                span
+            })
+            .collect()
+    }
+}
+
+/// Check if the line looks like a git trailer (`Token: value`)
+fn is_trailer(line: &str) -> bool {
+    line.split_once(": ").is_some_and(|(token, value)| {
+        !token.is_empty()
+            && !value.trim().is_empty()
+            && token.starts_with(|c: char| c.is_ascii_alphanumeric())
+            && token.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    })
+}
+
+/// Check if the line looks like the start of a footer: a git trailer (`Token: value`),
+/// a reference (`Closes #123`) or a breaking change
+pub(crate) fn is_footer_line(line: &str) -> bool {
+    let is_token = |token: &str| {
+        token.starts_with(|c: char| c.is_ascii_alphanumeric())
+            && token.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    };
+
+    is_trailer(line)
+        || BREAKING_TOKENS.iter().any(|token| line.starts_with(token))
+        || line
+            .split_once(" #")
+            .is_some_and(|(token, value)| is_token(token) && !value.trim().is_empty())
 }
 
 impl Default for Commit<'_> {
@@ -83,22 +422,87 @@ impl Default for Commit<'_> {
     }
 }
 
-pub fn parse_commit(commit_msg: &str) -> Commit {
-    let pairs = CommitParser::parse(Rule::commit, commit_msg).unwrap_or_else(|e| panic!("{}", e));
-    println!("{:#?}", pairs);
+/// Turn a pest error into a report which points at the position where the parsing failed
+fn parse_error(commit_msg: &str, error: Error<Rule>) -> Report {
+    // the rule names in the message are replaced by what the user has to write
+    let error = error.renamed_rules(|rule| match rule {
+        Rule::breaking => String::from("`!`"),
+        Rule::colon => String::from("`:`"),
+        Rule::space => String::from("a space after `:`"),
+        Rule::closing_brace => String::from("`)`"),
+        Rule::commit_type => String::from("type"),
+        rule => format!("{:?}", rule).replace('_', " "),
+    });
+    let span = match error.location {
+        InputLocation::Pos(pos) => LabeledSpan::at_offset(pos, error.variant.message()),
+        InputLocation::Span((start, end)) => LabeledSpan::at(start..end, error.variant.message()),
+    };
+
+    miette!(
+        severity = miette::Severity::Error,
+        labels = vec![span],
+        help = "commit message must look like `type(scope): subject`, optionally followed by a body and a footer separated by empty lines",
+        code = "parser",
+        "Commit message could not be parsed",
+    )
+    .with_source_code(commit_msg.to_string())
+}
+
+/// Parse the commit message with the default parser options
+pub fn parse_commit(commit_msg: &str) -> Result<Commit<'_>, Report> {
+    parse_commit_with(commit_msg, &ParserOptions::default())
+}
+
+/// Parse the commit message with the given parser options
+pub fn parse_commit_with<'a>(
+    commit_msg: &'a str,
+    options: &ParserOptions,
+) -> Result<Commit<'a>, Report> {
+    parse(commit_msg, options, false)
+}
+
+/// Parse the commit message like `parse_commit_with`, but also accept a body without a leading blank line,
+/// so that the missing blank line can be fixed
+pub(crate) fn parse_commit_loose<'a>(
+    commit_msg: &'a str,
+    options: &ParserOptions,
+) -> Result<Commit<'a>, Report> {
+    parse(commit_msg, options, true)
+}
+
+fn parse<'a>(
+    commit_msg: &'a str,
+    options: &ParserOptions,
+    loose: bool,
+) -> Result<Commit<'a>, Report> {
+    let header_regex = options.header_regex();
+    let rule = match (header_regex, loose) {
+        (Some(_), false) => Rule::any_commit,
+        (Some(_), true) => Rule::loose_any_commit,
+        (None, false) => Rule::commit,
+        (None, true) => Rule::loose_commit,
+    };
+    let pairs =
+        CommitParser::parse(rule, commit_msg).map_err(|error| parse_error(commit_msg, error))?;
 
     let mut commit = Commit {
         header: CommitSpan::new("", 0, 0),
         body: None,
         footer: None,
+        emoji: None,
         commit_type: CommitSpan::new("", 0, 0),
         scope: None,
+        scopes: vec![],
+        breaking: None,
         subject: CommitSpan::new("", 0, 0),
+        references: parse_references(commit_msg, options),
         raw: String::from(""),
     };
 
     for pair in pairs {
-        if let Rule::commit = pair.as_rule() {
+        if let Rule::commit | Rule::any_commit | Rule::loose_commit | Rule::loose_any_commit =
+            pair.as_rule()
+        {
             commit.raw = pair.as_str().to_string();
 
             for inner_pair in pair.into_inner() {
@@ -108,12 +512,18 @@ pub fn parse_commit(commit_msg: &str) -> Commit {
 
                         for header_pair in inner_pair.into_inner() {
                             match header_pair.as_rule() {
+                                Rule::emoji => {
+                                    commit.emoji = Some(CommitSpan::from(header_pair.as_span()))
+                                }
                                 Rule::commit_type => {
                                     commit.commit_type = CommitSpan::from(header_pair.as_span())
                                 }
                                 Rule::scope => {
                                     commit.scope = Some(CommitSpan::from(header_pair.as_span()))
                                 }
+                                Rule::breaking => {
+                                    commit.breaking = Some(CommitSpan::from(header_pair.as_span()))
+                                }
                                 Rule::subject => {
                                     commit.subject = CommitSpan::from(header_pair.as_span())
                                 }
@@ -121,7 +531,8 @@ pub fn parse_commit(commit_msg: &str) -> Commit {
                             }
                         }
                     }
-                    Rule::body_with_newlines => {
+                    Rule::any_header => commit.header = CommitSpan::from(inner_pair.as_span()),
+                    Rule::body_with_newlines | Rule::loose_body_with_newlines => {
                         for body_pair in inner_pair.into_inner() {
                             if body_pair.as_rule() == Rule::body {
                                 commit.body = Some(CommitSpan::from(body_pair.as_span()))
@@ -146,7 +557,151 @@ pub fn parse_commit(commit_msg: &str) -> Commit {
         }
     }
 
-    commit
+    if let Some((regex, format)) = header_regex {
+        parse_header(&mut commit, commit_msg, regex, format)?;
+    }
+
+    let type_chars = &options.type_chars;
+    if let Some((offset, c)) = commit
+        .commit_type
+        .input
+        .char_indices()
+        .find(|(_, c)| !type_chars.matches(*c))
+    {
+        let start = commit.commit_type.start + offset;
+        return Err(invalid_header_part(
+            commit_msg,
+            LabeledSpan::at(start..start + c.len_utf8(), "not allowed in a type"),
+            format!("types may only contain the characters `{}`", type_chars),
+        ));
+    }
+
+    if let Some(scope) = &commit.scope {
+        commit.scopes = split_scopes(commit_msg, scope, options)?;
+    }
+
+    Ok(commit)
+}
+
+/// Fill the parts of the header from the named groups of the regex
+fn parse_header<'a>(
+    commit: &mut Commit<'a>,
+    commit_msg: &'a str,
+    regex: &Regex,
+    format: &str,
+) -> Result<(), Report> {
+    let header = commit.header.input;
+    let offset = commit.header.start;
+
+    let captures = regex
+        .captures(header)
+        .filter(|captures| captures.name("type").is_some() && captures.name("subject").is_some())
+        .ok_or_else(|| {
+            invalid_header_part(
+                commit_msg,
+                LabeledSpan::at(offset..commit.header.end, "doesn't match the header format"),
+                format!("header must look like `{}`", format),
+            )
+        })?;
+    let span = |found: regex::Match<'a>| {
+        CommitSpan::new(found.as_str(), offset + found.start(), offset + found.end())
+    };
+
+    commit.emoji = captures.name("emoji").map(span);
+    commit.commit_type = span(captures.name("type").unwrap());
+    commit.scope = captures.name("scope").map(span);
+    commit.breaking = captures.name("breaking").map(span);
+    commit.subject = span(captures.name("subject").unwrap());
+
+    Ok(())
+}
+
+/// Report for a part of the header which doesn't fit the parser options
+fn invalid_header_part(commit_msg: &str, label: LabeledSpan, help: String) -> Report {
+    miette!(
+        severity = miette::Severity::Error,
+        labels = vec![label],
+        help = help,
+        code = "parser",
+        "Commit message could not be parsed",
+    )
+    .with_source_code(commit_msg.to_string())
+}
+
+/// Split the scope at the delimiters into the individual scopes, whitespace around them is ignored.
+/// Fails for empty scopes and scopes with characters which are not allowed.
+fn split_scopes<'a>(
+    commit_msg: &'a str,
+    scope: &CommitSpan<'a>,
+    options: &ParserOptions,
+) -> Result<Vec<CommitSpan<'a>>, Report> {
+    // longer delimiters first, so that `::` is preferred over `:`
+    let mut delimiters: Vec<&str> = options
+        .scope_delimiters
+        .iter()
+        .map(String::as_str)
+        .filter(|delimiter| !delimiter.is_empty())
+        .collect();
+    delimiters.sort_by_key(|delimiter| std::cmp::Reverse(delimiter.len()));
+
+    let text = scope.input;
+    let mut ranges = vec![];
+    let (mut part_start, mut i) = (0, 0);
+    while let Some(c) = text[i..].chars().next() {
+        match delimiters
+            .iter()
+            .find(|delimiter| text[i..].starts_with(**delimiter))
+        {
+            Some(delimiter) => {
+                ranges.push(part_start..i);
+                i += delimiter.len();
+                part_start = i;
+            }
+            None => i += c.len_utf8(),
+        }
+    }
+    ranges.push(part_start..text.len());
+
+    let invalid_scope = |label: LabeledSpan| {
+        invalid_header_part(
+            commit_msg,
+            label,
+            format!(
+                "scopes may only contain the characters `{}`, multiple scopes are separated by {}",
+                options.scope_chars,
+                delimiters
+                    .iter()
+                    .map(|delimiter| format!("`{}`", delimiter))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        )
+    };
+
+    let mut scopes = vec![];
+    for range in ranges {
+        let part = &text[range.clone()];
+        let start = scope.start + range.start + (part.len() - part.trim_start().len());
+        let part = part.trim();
+        if part.is_empty() {
+            return Err(invalid_scope(LabeledSpan::at_offset(
+                scope.start + range.start,
+                "empty scope",
+            )));
+        }
+        if let Some((offset, c)) = part
+            .char_indices()
+            .find(|(_, c)| !options.scope_chars.matches(*c))
+        {
+            return Err(invalid_scope(LabeledSpan::at(
+                start + offset..start + offset + c.len_utf8(),
+                "not allowed in a scope",
+            )));
+        }
+        scopes.push(CommitSpan::new(part, start, start + part.len()));
+    }
+
+    Ok(scopes)
 }
 
 #[cfg(test)]
@@ -204,6 +759,20 @@ mod tests {
                 ),
                 want_err: false,
             },
+            TestConfig {
+                name: String::from("multiple body paragraphs"),
+                commit: String::from(
+                    "feat(nice): add cool feature\n\nfirst paragraph\n\nsecond paragraph\n\nthe real footer",
+                ),
+                want_err: false,
+            },
+            TestConfig {
+                name: String::from("multiple footer lines"),
+                commit: String::from(
+                    "feat(nice): add cool feature\n\nsome body\n\nBREAKING CHANGE: removed api\nCloses #123\n",
+                ),
+                want_err: false,
+            },
             TestConfig {
                 name: String::from("breaking change after type"),
                 commit: String::from("feat!: add cool feature\n\nsome body"),
@@ -229,6 +798,28 @@ mod tests {
                 commit: String::from("feat: add cool feature\nsome body"),
                 want_err: true,
             },
+            TestConfig {
+                name: String::from("references"),
+                commit: String::from(
+                    "fix(nice): fix crash (#12)\n\nsee issue#1 and prefixes #2\n\nCloses #123\nresolves: #45",
+                ),
+                want_err: false,
+            },
+            TestConfig {
+                name: String::from("multiple scopes"),
+                commit: String::from("feat(api, ui/deps-dev): add cool feature"),
+                want_err: false,
+            },
+            TestConfig {
+                name: String::from("emoji"),
+                commit: String::from("✨ feat(ui): add cool feature"),
+                want_err: false,
+            },
+            TestConfig {
+                name: String::from("emoji shortcode"),
+                commit: String::from(":bug: fix: fix crash"),
+                want_err: false,
+            },
             TestConfig {
                 name: String::from("random text"),
                 commit: String::from("Added a cool new feature"),
@@ -251,8 +842,13 @@ mod tests {
                     test_config.name,
                     parse_result
                 );
+                assert!(
+                    parse_commit(&test_config.commit).is_err(),
+                    "{} | parse_commit should return a report",
+                    test_config.name
+                );
             } else {
-                let result = parse_commit(&test_config.commit);
+                let result = parse_commit(&test_config.commit).unwrap();
                 assert!(
                     parse_result.is_ok(),
                     "{} | commit parse should be successfull",
@@ -262,4 +858,320 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_reference_options() {
+        let options = ParserOptions {
+            issue_prefixes: vec![String::from("GH-"), String::from("PROJ-")],
+            reference_actions: vec![String::from("refs")],
+            ..Default::default()
+        };
+        let commit = parse_commit_with(
+            "fix: crash on start\n\nRefs GH-7, PROJ-789 and #12",
+            &options,
+        )
+        .unwrap();
+
+        let references: Vec<_> = commit
+            .references
+            .iter()
+            .map(|reference| {
+                (
+                    reference.action.as_ref().map(|action| action.to_string()),
+                    reference.prefix.to_string(),
+                    reference.issue.to_string(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            references,
+            vec![
+                (Some("Refs".to_string()), "GH-".to_string(), "7".to_string()),
+                (None, "PROJ-".to_string(), "789".to_string()),
+            ]
+        );
+        assert_eq!(commit.references[0].span.start(), 21);
+        assert_eq!(commit.references[0].span.end(), 30);
+    }
+
+    #[test]
+    fn test_reference_options_from_config() {
+        let options: ParserOptions =
+            serde_json::from_str(r#"{"issue-prefixes": ["GH-"]}"#).unwrap();
+
+        assert_eq!(options.issue_prefixes, ["GH-"]);
+        // missing options keep their defaults
+        assert!(options.reference_actions.contains(&String::from("closes")));
+    }
+
+    #[test]
+    fn test_scope_delimiters() {
+        let options = ParserOptions {
+            scope_delimiters: vec![String::from("::"), String::from(":")],
+            ..Default::default()
+        };
+        let commit = parse_commit_with("feat(api::users:ui): add feature", &options).unwrap();
+
+        let scopes: Vec<_> = commit
+            .scopes
+            .iter()
+            .map(|scope| (scope.to_string(), scope.start(), scope.end()))
+            .collect();
+        assert_eq!(
+            scopes,
+            [
+                (String::from("api"), 5, 8),
+                (String::from("users"), 10, 15),
+                (String::from("ui"), 16, 18)
+            ]
+        );
+
+        // `,` is not a delimiter with these options
+        assert!(parse_commit_with("feat(api,ui): add feature", &options).is_err());
+    }
+
+    #[test]
+    fn test_invalid_scopes() {
+        let report = parse_commit("feat(api,): add feature").err().unwrap();
+        let label = report.labels().unwrap().next().unwrap();
+        assert_eq!(label.label(), Some("empty scope"));
+        assert_eq!(label.offset(), 9);
+
+        let report = parse_commit("feat(api,u i): add feature").err().unwrap();
+        let label = report.labels().unwrap().next().unwrap();
+        assert_eq!(label.label(), Some("not allowed in a scope"));
+        assert_eq!(label.offset(), 10);
+
+        let report = parse_commit("feat(api,ui#): add feature").err().unwrap();
+        let label = report.labels().unwrap().next().unwrap();
+        assert_eq!(label.label(), Some("not allowed in a scope"));
+        assert_eq!(label.offset(), 11);
+    }
+
+    #[test]
+    fn test_unicode_type_and_scopes() {
+        let commit =
+            parse_commit("fonctionnalité(i18n-de,@org.pkg,ui_kit,モバイル): ajoute").unwrap();
+        assert_eq!(commit.commit_type.to_string(), "fonctionnalité");
+        let scopes: Vec<_> = commit.scopes.iter().map(|s| s.to_string()).collect();
+        assert_eq!(scopes, ["i18n-de", "@org.pkg", "ui_kit", "モバイル"]);
+
+        let report = parse_commit("fe#at: add feature").err().unwrap();
+        let label = report.labels().unwrap().next().unwrap();
+        assert_eq!(label.label(), Some("not allowed in a type"));
+        assert_eq!(label.offset(), 2);
+    }
+
+    #[test]
+    fn test_emojis() {
+        let emoji = |message| {
+            parse_commit(message)
+                .unwrap()
+                .emoji
+                .map(|emoji| emoji.to_string())
+        };
+
+        assert_eq!(
+            emoji("♻\u{fe0f} refactor: simplify"),
+            Some("♻\u{fe0f}".into())
+        );
+        assert_eq!(
+            emoji("👩\u{200d}💻 chore: setup"),
+            Some("👩\u{200d}💻".into())
+        );
+        assert_eq!(emoji("👍🏽 feat: add reactions"), Some("👍🏽".into()));
+        assert_eq!(emoji("✨feat: add feature"), Some("✨".into()));
+        assert_eq!(
+            emoji(":sparkles: feat: add feature"),
+            Some(":sparkles:".into())
+        );
+        assert_eq!(emoji("feat: ✨ add feature"), None);
+
+        let commit = parse_commit("✨ feat(ui): add feature").unwrap();
+        assert_eq!(commit.commit_type.to_string(), "feat");
+        assert_eq!(commit.scopes[0].start(), 9);
+
+        assert!(parse_commit("1 fix: fix crash").is_err());
+    }
+
+    #[test]
+    fn test_breaking_change() {
+        let commit = parse_commit("feat(api)!: remove endpoint").unwrap();
+        assert_eq!(commit.breaking.unwrap().start(), 9);
+
+        assert!(parse_commit("feat(api): add endpoint")
+            .unwrap()
+            .breaking
+            .is_none());
+
+        let report = parse_commit("Add endpoint").err().unwrap();
+        let label = report.labels().unwrap().next().unwrap();
+        assert_eq!(label.label(), Some("expected `!` or `:`"));
+    }
+
+    #[test]
+    fn test_missing_header_tokens() {
+        let label = |msg: &str| {
+            let report = parse_commit(msg).err().unwrap();
+            let label = report.labels().unwrap().next().unwrap();
+            (label.label().unwrap().to_string(), label.offset())
+        };
+
+        assert_eq!(
+            label("feat:x"),
+            (String::from("expected a space after `:`"), 5)
+        );
+        assert_eq!(label("feat(api: x"), (String::from("expected `)`"), 11));
+        assert_eq!(
+            label("feat(api)x: y"),
+            (String::from("expected `!` or `:`"), 9)
+        );
+    }
+
+    #[test]
+    fn test_breaking_change_description() {
+        let commit = parse_commit("feat(api)!: remove endpoint").unwrap();
+        assert_eq!(commit.breaking_change(), Some("remove endpoint"));
+
+        let commit =
+            parse_commit("feat: add endpoint\n\nBREAKING CHANGE: the old one is gone").unwrap();
+        assert_eq!(commit.breaking_change(), Some("the old one is gone"));
+
+        let commit = parse_commit("feat: add endpoint\n\nsome body").unwrap();
+        assert_eq!(commit.breaking_change(), None);
+    }
+
+    #[test]
+    fn test_header_formats() {
+        let parse = |format: &str, message| {
+            let options: ParserOptions =
+                serde_json::from_str(&format!(r#"{{"header-format": "{}"}}"#, format)).unwrap();
+            parse_commit_with(message, &options).map(|commit| {
+                (
+                    commit.commit_type.to_string(),
+                    commit
+                        .scopes
+                        .iter()
+                        .map(|s| s.to_string())
+                        .collect::<Vec<_>>(),
+                    commit.subject.to_string(),
+                    commit.subject.start(),
+                )
+            })
+        };
+
+        assert_eq!(
+            parse("bracket", "[FEAT] add feature\n\nsome body").unwrap(),
+            ("FEAT".into(), vec![], "add feature".into(), 7)
+        );
+        assert_eq!(
+            parse("ticket", "PROJ-123: fix login").unwrap(),
+            ("PROJ".into(), vec!["123".into()], "fix login".into(), 10)
+        );
+        assert_eq!(
+            parse("angular", "fix(core): fix crash").unwrap(),
+            ("fix".into(), vec!["core".into()], "fix crash".into(), 11)
+        );
+        assert!(parse("angular", "chore: update deps").is_err());
+        assert!(parse("angular", "fix(core):  fix crash").is_err());
+
+        let report = parse("bracket", "feat: add feature").err().unwrap();
+        assert_eq!(
+            report.help().unwrap().to_string(),
+            "header must look like `[type] subject`"
+        );
+    }
+
+    #[test]
+    fn test_header_pattern() {
+        let options: ParserOptions = serde_json::from_str(
+            r#"{"header-pattern": "^(?P<type>\\w+)(?P<breaking>!)? - (?P<subject>.+)$"}"#,
+        )
+        .unwrap();
+        let commit = parse_commit_with("feat! - add feature\n\nCloses #1", &options).unwrap();
+
+        assert_eq!(commit.commit_type.to_string(), "feat");
+        assert_eq!(commit.breaking.unwrap().start(), 4);
+        assert_eq!(commit.subject.to_string(), "add feature");
+        assert_eq!(commit.body.unwrap().to_string(), "Closes #1");
+        assert!(parse_commit_with("feat: add feature", &options).is_err());
+
+        assert!(
+            serde_json::from_str::<ParserOptions>(r#"{"header-pattern": "(?P<type>\\w+)"}"#)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_default_scope_delimiters() {
+        let commit = parse_commit("feat(@org/pkg,api/users): add feature").unwrap();
+
+        let scopes: Vec<_> = commit
+            .scopes
+            .iter()
+            .map(|scope| scope.to_string())
+            .collect();
+        assert_eq!(scopes, ["@org/pkg", "api/users"]);
+    }
+
+    #[test]
+    fn test_char_classes_from_config() {
+        let options: ParserOptions = serde_json::from_str(
+            r#"{"type-chars": "[a-z]", "scope-chars": "[a-z/@]", "scope-delimiters": [","]}"#,
+        )
+        .unwrap();
+
+        let commit = parse_commit_with("feat(@org/pkg,ui): add feature", &options).unwrap();
+        assert_eq!(commit.scopes[0].to_string(), "@org/pkg");
+        assert!(parse_commit_with("feat(ui-kit): add feature", &options).is_err());
+        assert!(parse_commit_with("Feat: add feature", &options).is_err());
+
+        assert!(serde_json::from_str::<ParserOptions>(r#"{"type-chars": "[a-"}"#).is_err());
+    }
+
+    #[test]
+    fn test_trailers() {
+        let commit = parse_commit(
+            "feat(foo): add feature\n\nsome body\n\nReviewed-by: Jane Doe\nno trailer\nSigned-off-by: Jane Doe <jane@example.com>\n",
+        )
+        .unwrap();
+        let trailers = commit.trailers();
+
+        assert_eq!(trailers.len(), 2);
+        assert_eq!(trailers[0].to_string(), "Reviewed-by: Jane Doe");
+        assert_eq!(trailers[0].start(), 35);
+        assert_eq!(
+            trailers[1].to_string(),
+            "Signed-off-by: Jane Doe <jane@example.com>"
+        );
+        assert_eq!(
+            &commit.raw[trailers[1].start()..trailers[1].end()],
+            "Signed-off-by: Jane Doe <jane@example.com>"
+        );
+
+        // the trailers can also be in the only paragraph after the header
+        let commit = parse_commit("feat(foo): add feature\n\nCloses: #123").unwrap();
+        assert_eq!(commit.trailers().len(), 1);
+
+        // the header is never a trailer
+        let commit = parse_commit("feat(foo): add feature").unwrap();
+        assert!(commit.trailers().is_empty());
+    }
+
+    #[test]
+    fn test_trailers_with_crlf() {
+        let commit = parse_commit(
+            "feat(foo): add feature\r\n\r\nsome body\r\n\r\nReviewed-by: Jane Doe\r\nSigned-off-by: Jane Doe <jane@example.com>\r\n",
+        )
+        .unwrap();
+        let trailers = commit.trailers();
+
+        assert_eq!(trailers.len(), 2);
+        assert_eq!(trailers[0].to_string(), "Reviewed-by: Jane Doe");
+        assert_eq!(trailers[0].start(), 39);
+        assert_eq!(
+            &commit.raw[trailers[1].start()..trailers[1].end()],
+            "Signed-off-by: Jane Doe <jane@example.com>"
+        );
+    }
 }
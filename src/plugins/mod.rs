@@ -0,0 +1,231 @@
+use std::path::{Path, PathBuf};
+
+use config::Value;
+use miette::{miette, LabeledSpan, MietteDiagnostic, Report};
+use serde::{de, Deserialize, Deserializer};
+
+use crate::{
+    parser::Commit,
+    registry::invalid_options,
+    rules::{Condition, NoOpts, Rule, Severity},
+};
+
+#[cfg(feature = "script")]
+pub mod script;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+/// Plugin which provides a rule, configured in the `[plugins]` table by the name of the rule
+///
+/// ```toml
+/// [plugins]
+/// ticket-reference = { wasm = "plugins/ticket_reference.wasm" }
+/// no-wip = { script = "rules/no_wip.rhai" }
+/// ```
+#[derive(Debug, Deserialize)]
+pub enum PluginConfig {
+    /// Path to a WebAssembly module (relative to the config file)
+    #[serde(rename = "wasm")]
+    Wasm(PathBuf),
+    /// Path to a Rhai script (relative to the config file)
+    #[serde(rename = "script")]
+    Script(PathBuf),
+}
+
+/// Options of a plugin rule: the severity followed by any options, which are passed to the plugin
+#[derive(Debug)]
+pub struct PluginOpts(pub Severity, pub Vec<serde_json::Value>);
+
+impl<'de> Deserialize<'de> for PluginOpts {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut values = Vec::<serde_json::Value>::deserialize(deserializer)?;
+        if values.is_empty() {
+            return Err(de::Error::custom("the severity is missing"));
+        }
+        let severity = serde_json::from_value(values.remove(0)).map_err(de::Error::custom)?;
+
+        Ok(PluginOpts(severity, values))
+    }
+}
+
+impl Default for PluginOpts {
+    /// Options of a plugin which is not configured in the `[rules]` table
+    fn default() -> Self {
+        PluginOpts(Severity::Error, vec![])
+    }
+}
+
+/// Violation which is returned by a plugin
+#[derive(Debug, Deserialize)]
+#[cfg_attr(not(any(feature = "script", feature = "wasm")), allow(dead_code))]
+struct PluginDiagnostic {
+    message: String,
+    #[serde(default)]
+    help: Option<String>,
+    #[serde(default)]
+    labels: Vec<PluginLabel>,
+}
+
+/// Highlighted part of the commit message (byte offsets into the raw commit message)
+#[derive(Debug, Deserialize)]
+#[cfg_attr(not(any(feature = "script", feature = "wasm")), allow(dead_code))]
+struct PluginLabel {
+    start: usize,
+    end: usize,
+    #[serde(default)]
+    text: Option<String>,
+}
+
+/// Turn the violation which was returned by a plugin into a report
+#[cfg_attr(not(any(feature = "script", feature = "wasm")), allow(dead_code))]
+fn plugin_report(
+    name: &str,
+    severity: &Severity,
+    commit: &Commit,
+    diagnostic: PluginDiagnostic,
+) -> Report {
+    // labels outside of the commit message can't be shown
+    let labels = diagnostic
+        .labels
+        .into_iter()
+        .filter(|label| {
+            label.start <= label.end
+                && commit.raw.is_char_boundary(label.start)
+                && commit.raw.is_char_boundary(label.end)
+        })
+        .map(|label| LabeledSpan::new(label.text, label.start, label.end - label.start));
+
+    let mut report = MietteDiagnostic::new(diagnostic.message)
+        .with_code(format!("rule/{}", name))
+        .with_severity(match severity {
+            Severity::Warning => miette::Severity::Warning,
+            Severity::Error => miette::Severity::Error,
+            Severity::Off => miette::Severity::Advice,
+        })
+        .with_labels(labels);
+    if let Some(help) = diagnostic.help {
+        report = report.with_help(help);
+    }
+
+    Report::new(report).with_source_code(commit.raw.clone())
+}
+
+/// Report for a plugin which could not be run, with the severity of its rule
+#[cfg_attr(not(any(feature = "script", feature = "wasm")), allow(dead_code))]
+fn plugin_failure(name: &str, severity: &Severity, error: impl std::fmt::Display) -> Report {
+    miette!(
+        severity = match severity {
+            Severity::Warning => miette::Severity::Warning,
+            Severity::Error => miette::Severity::Error,
+            Severity::Off => miette::Severity::Advice,
+        },
+        code = "plugin",
+        "Plugin `{}` failed: {}",
+        name,
+        error
+    )
+}
+
+/// Report for a plugin which needs a feature that was not compiled in
+#[cfg_attr(all(feature = "script", feature = "wasm"), allow(dead_code))]
+fn missing_feature(name: &str, path: &Path, feature: &str) -> Report {
+    miette!(
+        code = "plugin",
+        "Plugin `{}` ({}) needs the `{}` feature of commitguard",
+        name,
+        path.display(),
+        feature
+    )
+}
+
+/// Create the rule of a plugin from its options in the `[rules]` table (if any).
+/// Relative paths are resolved from `base_dir`.
+pub fn create_rule(
+    name: &str,
+    plugin: &PluginConfig,
+    value: Option<Value>,
+    base_dir: Option<&Path>,
+) -> Result<Box<dyn Rule>, Report> {
+    let resolve = |path: &PathBuf| base_dir.map_or_else(|| path.clone(), |dir| dir.join(path));
+    // rule names are static, plugins are loaded once per config, so leaking the name is fine
+    let name: &'static str = Box::leak(name.to_string().into_boxed_str());
+
+    match plugin {
+        PluginConfig::Wasm(path) => {
+            let path = resolve(path);
+            let opts = match value {
+                Some(value) => value
+                    .try_deserialize::<PluginOpts>()
+                    .map_err(|e| invalid_options(name, e))?,
+                None => PluginOpts::default(),
+            };
+
+            #[cfg(feature = "wasm")]
+            return Ok(Box::new(wasm::WasmRule::load(name, &path, opts)?));
+
+            #[cfg(not(feature = "wasm"))]
+            {
+                let _ = opts;
+                Err(missing_feature(name, &path, "wasm"))
+            }
+        }
+        PluginConfig::Script(path) => {
+            let path = resolve(path);
+            let opts = match value {
+                Some(value) => value
+                    .try_deserialize::<NoOpts>()
+                    .map_err(|e| invalid_options(name, e))?,
+                None => NoOpts(Severity::Error, Condition::Always),
+            };
+
+            #[cfg(feature = "script")]
+            return Ok(Box::new(script::ScriptRule::load(name, &path, opts)?));
+
+            #[cfg(not(feature = "script"))]
+            {
+                let _ = opts;
+                Err(missing_feature(name, &path, "script"))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_commit;
+
+    use super::*;
+
+    #[test]
+    fn test_plugin_opts() {
+        let opts: PluginOpts = serde_json::from_str(r#"["warning", "always", 42]"#).unwrap();
+        assert_eq!(opts.0, Severity::Warning);
+        assert_eq!(
+            opts.1,
+            vec![serde_json::json!("always"), serde_json::json!(42)]
+        );
+
+        assert!(serde_json::from_str::<PluginOpts>("[]").is_err());
+        assert!(serde_json::from_str::<PluginOpts>(r#"["fatal"]"#).is_err());
+    }
+
+    #[test]
+    fn test_plugin_report() {
+        let commit = parse_commit("feat(foo): add feature").unwrap();
+        let diagnostic = serde_json::from_str(
+            r#"{"message": "Ticket is missing", "help": "add a ticket", "labels": [{"start": 11, "end": 22, "text": "here"}, {"start": 0, "end": 100}]}"#,
+        )
+        .unwrap();
+
+        let report = plugin_report("ticket", &Severity::Warning, &commit, diagnostic);
+
+        assert_eq!(report.to_string(), "Ticket is missing");
+        assert_eq!(report.severity(), Some(miette::Severity::Warning));
+        assert_eq!(report.code().unwrap().to_string(), "rule/ticket");
+        assert_eq!(report.help().unwrap().to_string(), "add a ticket");
+        let labels: Vec<_> = report.labels().unwrap().collect();
+        assert_eq!(labels.len(), 1);
+        assert_eq!(labels[0].offset(), 11);
+        assert_eq!(labels[0].len(), 11);
+    }
+}
@@ -0,0 +1,224 @@
+//! Rules from Rhai scripts
+//!
+//! A script gets the `commit` (with the same fields as [`Commit`], every part is an object map
+//! with `input`, `start` and `end`) and the configured `condition` (`"always"` or `"never"`).
+//! It returns `()` if the commit message passed the rule, otherwise a message or an object map
+//! `#{ message: "...", help: "...", labels: [#{ start: 0, end: 4, text: "..." }] }`.
+//!
+//! ```rhai
+//! if commit.subject.input.contains("WIP") {
+//!     #{ message: "Work in progress", labels: [#{ start: commit.subject.start, end: commit.subject.end }] }
+//! }
+//! ```
+//!
+//! Scripts can't import modules and the number of operations they can run is limited.
+
+use std::path::Path;
+
+use miette::{miette, Report};
+use rhai::{module_resolvers::DummyModuleResolver, Dynamic, Engine, Scope, AST};
+
+use crate::{
+    parser::Commit,
+    rules::{Condition, NoOpts, Rule, Severity},
+};
+
+use super::{plugin_failure, plugin_report, PluginDiagnostic};
+
+/// Maximum number of operations a script can run for one commit message
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// Rule which is implemented by a Rhai script
+pub struct ScriptRule {
+    name: &'static str,
+    opts: NoOpts,
+    engine: Engine,
+    ast: AST,
+}
+
+impl ScriptRule {
+    /// Compile the script at the given path
+    pub fn load(name: &'static str, path: &Path, opts: NoOpts) -> Result<Self, Report> {
+        let script = std::fs::read_to_string(path).map_err(|e| {
+            miette!(
+                code = "plugin",
+                "Could not read plugin `{}` from {}: {}",
+                name,
+                path.display(),
+                e
+            )
+        })?;
+
+        Self::from_script(name, &script, opts)
+    }
+
+    /// Compile the script from its source
+    pub fn from_script(name: &'static str, script: &str, opts: NoOpts) -> Result<Self, Report> {
+        let mut engine = Engine::new();
+        engine
+            .set_module_resolver(DummyModuleResolver::new())
+            .set_max_operations(MAX_OPERATIONS)
+            .on_print(|_| {})
+            .on_debug(|_, _, _| {});
+
+        let ast = engine
+            .compile(script)
+            .map_err(|e| miette!(code = "plugin", "Invalid plugin `{}`: {}", name, e))?;
+
+        Ok(ScriptRule {
+            name,
+            opts,
+            engine,
+            ast,
+        })
+    }
+
+    /// Run the script for the commit and return the violation, if there is one
+    fn call(&self, commit: &Commit) -> Result<Option<PluginDiagnostic>, Box<rhai::EvalAltResult>> {
+        let mut scope = Scope::new();
+        scope.push_constant("commit", rhai::serde::to_dynamic(commit)?);
+        scope.push_constant(
+            "condition",
+            match self.opts.1 {
+                Condition::Always => "always",
+                Condition::Never => "never",
+            },
+        );
+
+        let result = self
+            .engine
+            .eval_ast_with_scope::<Dynamic>(&mut scope, &self.ast)?;
+
+        if result.is_unit() {
+            Ok(None)
+        } else if result.is_string() {
+            Ok(Some(PluginDiagnostic {
+                message: result.into_string()?,
+                help: None,
+                labels: vec![],
+            }))
+        } else {
+            Ok(Some(rhai::serde::from_dynamic(&result)?))
+        }
+    }
+}
+
+impl Rule for ScriptRule {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn severity(&self) -> &Severity {
+        &self.opts.0
+    }
+
+    fn run(&self, commit: &Commit) -> Option<Report> {
+        if self.opts.0 == Severity::Off {
+            return None;
+        }
+
+        match self.call(commit) {
+            Ok(diagnostic) => diagnostic
+                .map(|diagnostic| plugin_report(self.name, &self.opts.0, commit, diagnostic)),
+            Err(e) => Some(plugin_failure(self.name, &self.opts.0, e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_commit;
+
+    use super::*;
+
+    const NO_WIP: &str = r#"
+        let is_wip = commit.subject.input.contains("WIP");
+        if (condition == "never") == is_wip {
+            #{
+                message: "Work in progress",
+                help: "finish the work before committing",
+                labels: [#{ start: commit.subject.start, end: commit.subject.end, text: "here" }]
+            }
+        }
+    "#;
+
+    fn rule(script: &str) -> ScriptRule {
+        ScriptRule::from_script("no-wip", script, NoOpts(Severity::Error, Condition::Never))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_passing_script() {
+        let commit = parse_commit("feat(foo): add feature").unwrap();
+
+        assert!(rule(NO_WIP).run(&commit).is_none());
+    }
+
+    #[test]
+    fn test_failing_script() {
+        let commit = parse_commit("feat(foo): WIP").unwrap();
+        let report = rule(NO_WIP).run(&commit).unwrap();
+
+        assert_eq!(report.to_string(), "Work in progress");
+        assert_eq!(report.code().unwrap().to_string(), "rule/no-wip");
+        assert_eq!(
+            report.help().unwrap().to_string(),
+            "finish the work before committing"
+        );
+        let labels: Vec<_> = report.labels().unwrap().collect();
+        assert_eq!(labels[0].offset(), 11);
+        assert_eq!(labels[0].len(), 3);
+    }
+
+    #[test]
+    fn test_condition() {
+        let commit = parse_commit("feat(foo): WIP").unwrap();
+        let rule =
+            ScriptRule::from_script("no-wip", NO_WIP, NoOpts(Severity::Error, Condition::Always))
+                .unwrap();
+
+        assert!(rule.run(&commit).is_none());
+    }
+
+    #[test]
+    fn test_message_only() {
+        let commit = parse_commit("feat(foo): add feature").unwrap();
+        let report = rule(r#"if commit.body == () { "Body is missing" }"#)
+            .run(&commit)
+            .unwrap();
+
+        assert_eq!(report.to_string(), "Body is missing");
+    }
+
+    #[test]
+    fn test_failing_script_run() {
+        let commit = parse_commit("feat(foo): add feature").unwrap();
+
+        let report = rule("loop {}").run(&commit).unwrap();
+        assert!(report.to_string().starts_with("Plugin `no-wip` failed"));
+
+        let report = rule(r#"import "fs" as fs;"#).run(&commit).unwrap();
+        assert!(report.to_string().starts_with("Plugin `no-wip` failed"));
+        assert_eq!(report.severity(), Some(miette::Severity::Error));
+
+        // the failure has the configured severity of the rule
+        let rule = ScriptRule::from_script(
+            "no-wip",
+            "loop {}",
+            NoOpts(Severity::Warning, Condition::Never),
+        )
+        .unwrap();
+        let report = rule.run(&commit).unwrap();
+        assert_eq!(report.severity(), Some(miette::Severity::Warning));
+    }
+
+    #[test]
+    fn test_invalid_script() {
+        let report =
+            ScriptRule::from_script("no-wip", "if {", NoOpts(Severity::Error, Condition::Never))
+                .err()
+                .unwrap();
+
+        assert!(report.to_string().starts_with("Invalid plugin `no-wip`"));
+    }
+}
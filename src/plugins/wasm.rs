@@ -0,0 +1,301 @@
+//! Rules from WebAssembly modules
+//!
+//! A module has to export:
+//!
+//! - `memory`: the linear memory
+//! - `alloc(len: i32) -> i32`: reserve `len` bytes and return a pointer to them
+//! - `lint(ptr: i32, len: i32) -> i64`: check the JSON input at `ptr` and return the JSON output
+//!   as `(ptr << 32) | len`, where a length of `0` means the commit message passed the rule
+//!
+//! The input is `{"commit": {...}, "options": [...]}` and the output is either `null` or
+//! `{"message": "...", "help": "...", "labels": [{"start": 0, "end": 4, "text": "..."}]}`.
+//!
+//! Modules are sandboxed: they can't import anything (so there is no filesystem or network access)
+//! and their fuel and memory is limited.
+
+use std::path::Path;
+
+use miette::{miette, Report};
+use serde::Serialize;
+use wasmtime::{Config, Engine, Instance, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+use crate::{
+    parser::Commit,
+    rules::{Rule, Severity},
+};
+
+use super::{plugin_failure, plugin_report, PluginDiagnostic, PluginOpts};
+
+/// Maximum number of instructions (roughly) a plugin can run for one commit message
+const MAX_FUEL: u64 = 100_000_000;
+
+/// Maximum memory of a plugin
+const MAX_MEMORY: usize = 64 * 1024 * 1024;
+
+/// Input which is passed to the module as JSON
+#[derive(Serialize)]
+struct PluginInput<'a> {
+    commit: &'a Commit<'a>,
+    options: &'a [serde_json::Value],
+}
+
+/// Serialize the commit and the options of the rule for the module
+fn plugin_input(commit: &Commit, options: &[serde_json::Value]) -> String {
+    serde_json::to_string(&PluginInput { commit, options })
+        .expect("commits and options can always be serialized")
+}
+
+/// Parse the JSON output of the module, an empty output or `null` means the commit message passed the rule
+fn parse_output(output: &str) -> Result<Option<PluginDiagnostic>, serde_json::Error> {
+    if output.trim().is_empty() {
+        return Ok(None);
+    }
+
+    serde_json::from_str(output)
+}
+
+/// Rule which is implemented by a WebAssembly module
+pub struct WasmRule {
+    name: &'static str,
+    opts: PluginOpts,
+    engine: Engine,
+    module: Module,
+}
+
+impl WasmRule {
+    /// Compile the module at the given path
+    pub fn load(name: &'static str, path: &Path, opts: PluginOpts) -> Result<Self, Report> {
+        let bytes = std::fs::read(path).map_err(|e| {
+            miette!(
+                code = "plugin",
+                "Could not read plugin `{}` from {}: {}",
+                name,
+                path.display(),
+                e
+            )
+        })?;
+
+        Self::from_bytes(name, &bytes, opts)
+    }
+
+    /// Compile the module from its binary or text format
+    pub fn from_bytes(name: &'static str, bytes: &[u8], opts: PluginOpts) -> Result<Self, Report> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).map_err(|e| plugin_failure(name, &opts.0, e))?;
+
+        let module = Module::new(&engine, bytes)
+            .map_err(|e| miette!(code = "plugin", "Invalid plugin `{}`: {}", name, e))?;
+        if let Some(import) = module.imports().next() {
+            return Err(miette!(
+                code = "plugin",
+                help = "plugins can't access anything outside of their sandbox",
+                "Invalid plugin `{}`: it imports `{}::{}`",
+                name,
+                import.module(),
+                import.name()
+            ));
+        }
+
+        Ok(WasmRule {
+            name,
+            opts,
+            engine,
+            module,
+        })
+    }
+
+    /// Run the `lint` function of the module with the input and return its output
+    fn call(&self, input: &str) -> wasmtime::Result<String> {
+        let mut store = Store::new(
+            &self.engine,
+            StoreLimitsBuilder::new().memory_size(MAX_MEMORY).build(),
+        );
+        store.limiter(|limits: &mut StoreLimits| limits);
+        store.set_fuel(MAX_FUEL)?;
+
+        let instance = Instance::new(&mut store, &self.module, &[])?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| wasmtime::Error::msg("the module doesn't export `memory`"))?;
+        let alloc = instance.get_typed_func::<i32, i32>(&mut store, "alloc")?;
+        let lint = instance.get_typed_func::<(i32, i32), i64>(&mut store, "lint")?;
+
+        let len = i32::try_from(input.len())?;
+        let ptr = alloc.call(&mut store, len)?;
+        memory.write(&mut store, usize::try_from(ptr)?, input.as_bytes())?;
+
+        let result = lint.call(&mut store, (ptr, len))? as u64;
+        let (ptr, len) = ((result >> 32) as usize, (result & 0xffff_ffff) as usize);
+
+        let mut output = vec![0; len];
+        memory.read(&store, ptr, &mut output)?;
+
+        Ok(String::from_utf8(output)?)
+    }
+}
+
+impl Rule for WasmRule {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn severity(&self) -> &Severity {
+        &self.opts.0
+    }
+
+    fn run(&self, commit: &Commit) -> Option<Report> {
+        if self.opts.0 == Severity::Off {
+            return None;
+        }
+
+        let output = match self.call(&plugin_input(commit, &self.opts.1)) {
+            Ok(output) => output,
+            Err(e) => return Some(plugin_failure(self.name, &self.opts.0, e)),
+        };
+
+        match parse_output(&output) {
+            Ok(diagnostic) => diagnostic
+                .map(|diagnostic| plugin_report(self.name, &self.opts.0, commit, diagnostic)),
+            Err(e) => Some(plugin_failure(self.name, &self.opts.0, e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_commit;
+
+    use super::*;
+
+    /// Module in the text format which returns the given output for every commit message
+    fn module_returning(output: &str) -> String {
+        format!(
+            r#"(module
+                (memory (export "memory") 1)
+                (global $next (mut i32) (i32.const 1024))
+                (data (i32.const 0) "{}")
+                (func (export "alloc") (param $len i32) (result i32)
+                    (local $ptr i32)
+                    (local.set $ptr (global.get $next))
+                    (global.set $next (i32.add (global.get $next) (local.get $len)))
+                    (local.get $ptr))
+                (func (export "lint") (param i32 i32) (result i64)
+                    (i64.const {})))"#,
+            output.replace('"', "\\\""),
+            output.len()
+        )
+    }
+
+    #[test]
+    fn test_plugin_input() {
+        let commit = parse_commit("feat(foo): add feature").unwrap();
+        let input: serde_json::Value =
+            serde_json::from_str(&plugin_input(&commit, &[serde_json::json!("always")])).unwrap();
+
+        assert_eq!(input["commit"]["scope"]["input"], "foo");
+        assert_eq!(input["commit"]["scope"]["start"], 5);
+        assert_eq!(input["options"], serde_json::json!(["always"]));
+    }
+
+    #[test]
+    fn test_parse_output() {
+        assert!(parse_output("").unwrap().is_none());
+        assert!(parse_output("null").unwrap().is_none());
+        assert!(parse_output("{").is_err());
+        assert_eq!(
+            parse_output(r#"{"message": "Ticket is missing"}"#)
+                .unwrap()
+                .unwrap()
+                .message,
+            "Ticket is missing"
+        );
+    }
+
+    fn opts() -> PluginOpts {
+        PluginOpts(Severity::Error, vec![])
+    }
+
+    #[test]
+    fn test_passing_plugin() {
+        let commit = parse_commit("feat(foo): add feature").unwrap();
+
+        let rule = WasmRule::from_bytes("ticket", module_returning("").as_bytes(), opts()).unwrap();
+        assert!(rule.run(&commit).is_none());
+
+        let rule =
+            WasmRule::from_bytes("ticket", module_returning("null").as_bytes(), opts()).unwrap();
+        assert!(rule.run(&commit).is_none());
+    }
+
+    #[test]
+    fn test_failing_plugin() {
+        let commit = parse_commit("feat(foo): add feature").unwrap();
+        let module = module_returning(
+            r#"{"message": "Ticket is missing", "labels": [{"start": 11, "end": 22}]}"#,
+        );
+
+        let rule = WasmRule::from_bytes("ticket", module.as_bytes(), opts()).unwrap();
+        let report = rule.run(&commit).unwrap();
+
+        assert_eq!(report.to_string(), "Ticket is missing");
+        assert_eq!(report.code().unwrap().to_string(), "rule/ticket");
+        assert_eq!(rule.name(), "ticket");
+    }
+
+    #[test]
+    fn test_invalid_output() {
+        let commit = parse_commit("feat(foo): add feature").unwrap();
+
+        let rule =
+            WasmRule::from_bytes("ticket", module_returning("{").as_bytes(), opts()).unwrap();
+        let report = rule.run(&commit).unwrap();
+
+        assert!(report.to_string().starts_with("Plugin `ticket` failed"));
+        assert_eq!(report.severity(), Some(miette::Severity::Error));
+
+        // the failure has the configured severity of the rule
+        let rule = WasmRule::from_bytes(
+            "ticket",
+            module_returning("{").as_bytes(),
+            PluginOpts(Severity::Warning, vec![]),
+        )
+        .unwrap();
+        let report = rule.run(&commit).unwrap();
+
+        assert_eq!(report.severity(), Some(miette::Severity::Warning));
+    }
+
+    #[test]
+    fn test_endless_plugin() {
+        let commit = parse_commit("feat(foo): add feature").unwrap();
+        let module = r#"(module
+            (memory (export "memory") 1)
+            (func (export "alloc") (param i32) (result i32) (i32.const 0))
+            (func (export "lint") (param i32 i32) (result i64)
+                (loop $forever (br $forever))
+                (i64.const 0)))"#;
+
+        let rule = WasmRule::from_bytes("ticket", module.as_bytes(), opts()).unwrap();
+        let report = rule.run(&commit).unwrap();
+
+        assert!(report.to_string().starts_with("Plugin `ticket` failed"));
+    }
+
+    #[test]
+    fn test_imports_are_not_allowed() {
+        let module = r#"(module
+            (import "wasi_snapshot_preview1" "fd_write"
+                (func (param i32 i32 i32 i32) (result i32)))
+            (memory (export "memory") 1))"#;
+
+        let report = WasmRule::from_bytes("ticket", module.as_bytes(), opts())
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            report.to_string(),
+            "Invalid plugin `ticket`: it imports `wasi_snapshot_preview1::fd_write`"
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use serde::Serialize;
+
+use crate::parser::CommitSpan;
+
+/// A 1-based position in a commit message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Position {
+    /// Line of the position
+    pub line: usize,
+    /// Column counted in characters (Unicode scalar values)
+    pub column: usize,
+    /// Column counted in UTF-16 code units, like editors using the Language Server Protocol do
+    pub utf16_column: usize,
+}
+
+/// Index of the line starts of a message to convert byte offsets into positions.
+///
+/// It is built once per message and can then be used for all spans of the message:
+///
+/// ```
+/// use commitguard::{parse_commit, LineIndex};
+///
+/// let commit = parse_commit("feat: add feature\n\nsome body").unwrap();
+/// let index = LineIndex::new(&commit.raw);
+/// let (start, end) = index.span(commit.body.as_ref().unwrap());
+///
+/// assert_eq!((start.line, start.column), (3, 1));
+/// assert_eq!((end.line, end.column), (3, 10));
+/// ```
+#[derive(Debug, Clone)]
+pub struct LineIndex<'a> {
+    text: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(text: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(offset, _)| offset + 1))
+            .collect();
+
+        LineIndex { text, line_starts }
+    }
+
+    /// Position of the byte offset, offsets after the end of the text are moved to the end
+    pub fn position(&self, offset: usize) -> Position {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let line = self.line_starts.partition_point(|&start| start <= offset);
+        let before = &self.text[self.line_starts[line - 1]..offset];
+
+        Position {
+            line,
+            column: before.chars().count() + 1,
+            utf16_column: before.encode_utf16().count() + 1,
+        }
+    }
+
+    /// Positions of the start and the end of the span
+    pub fn span(&self, span: &CommitSpan) -> (Position, Position) {
+        (self.position(span.start()), self.position(span.end()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_commit;
+
+    use super::*;
+
+    fn position(line: usize, column: usize, utf16_column: usize) -> Position {
+        Position {
+            line,
+            column,
+            utf16_column,
+        }
+    }
+
+    #[test]
+    fn test_positions() {
+        let index = LineIndex::new("feat: add\n\nbody\n");
+
+        assert_eq!(index.position(0), position(1, 1, 1));
+        assert_eq!(index.position(9), position(1, 10, 10));
+        assert_eq!(index.position(10), position(2, 1, 1));
+        assert_eq!(index.position(13), position(3, 3, 3));
+        assert_eq!(index.position(16), position(4, 1, 1));
+        assert_eq!(index.position(100), position(4, 1, 1));
+    }
+
+    #[test]
+    fn test_unicode_columns() {
+        // `ä` has 2 bytes and 1 UTF-16 unit, `😀` has 4 bytes and 2 UTF-16 units
+        let index = LineIndex::new("feat: ä😀x");
+
+        assert_eq!(index.position(8), position(1, 8, 8));
+        assert_eq!(index.position(12), position(1, 9, 10));
+        // offsets inside a character are moved to its start
+        assert_eq!(index.position(10), position(1, 8, 8));
+    }
+
+    #[test]
+    fn test_span_positions() {
+        let commit = parse_commit("feat(api): add\n\nbody\n\nCloses #1").unwrap();
+        let index = LineIndex::new(&commit.raw);
+
+        assert_eq!(
+            index.span(commit.scope.as_ref().unwrap()),
+            (position(1, 6, 6), position(1, 9, 9))
+        );
+        assert_eq!(
+            index.span(commit.footer.as_ref().unwrap()),
+            (position(5, 1, 1), position(5, 10, 10))
+        );
+    }
+}
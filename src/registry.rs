@@ -0,0 +1,490 @@
+use std::collections::BTreeMap;
+
+use config::Value;
+use miette::{miette, Report};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    config::{merge_options, RulesConfig},
+    plugins,
+    rules::{
+        body_empty, body_leading_blank, body_pattern, co_authored_by, find_rule_meta,
+        footer_leading_blank, footer_pattern, header_emoji, header_pattern, header_trim,
+        references_empty, scope_case, scope_empty, scope_enum, scope_matches_paths,
+        scope_max_length, signed_off_by, subject_full_stop, subject_pattern, trailer_exists,
+        type_case, type_enum, Rule, TypeOverrides,
+    },
+};
+
+/// Creates a rule from the options in the config
+type RuleFactory = Box<dyn Fn(Value) -> Result<Box<dyn Rule>, config::ConfigError>>;
+
+/// Maps rule names to factories, which create the rule from its options in the config
+///
+/// The default registry contains all built-in rules. Additional rules can be registered
+/// and configured in the `[rules]` table like the built-in ones:
+///
+/// ```
+/// use commitguard::{
+///     config::RulesConfig,
+///     rules::{NoOpts, Severity},
+///     Commit, Linter, Rule, RuleRegistry,
+/// };
+///
+/// struct NoWipRule {
+///     opts: NoOpts,
+/// }
+///
+/// impl Rule for NoWipRule {
+///     fn name(&self) -> &'static str {
+///         "no-wip"
+///     }
+///
+///     fn severity(&self) -> &Severity {
+///         &self.opts.0
+///     }
+///
+///     fn run(&self, commit: &Commit) -> Option<miette::Report> {
+///         commit
+///             .subject
+///             .to_string()
+///             .contains("WIP")
+///             .then(|| miette::miette!(severity = miette::Severity::Error, "Work in progress"))
+///     }
+/// }
+///
+/// let mut registry = RuleRegistry::default();
+/// registry.register("no-wip", |opts| Box::new(NoWipRule { opts }));
+///
+/// let config = RulesConfig::from_toml(r#"
+///     [rules]
+///     no-wip = ["error", "never"]
+/// "#).unwrap();
+/// let linter = Linter::from_config_with_registry(config, &registry).unwrap();
+/// assert!(linter.lint("fix(foo): WIP").has_errors());
+/// ```
+pub struct RuleRegistry {
+    factories: BTreeMap<String, RuleFactory>,
+}
+
+impl RuleRegistry {
+    /// Registry without any rules.
+    /// The built-in rules are left out, unless the config changes their default options:
+    ///
+    /// ```
+    /// use commitguard::{
+    ///     config::RulesConfig,
+    ///     rules::{NoOpts, Severity},
+    ///     Commit, Linter, Rule, RuleRegistry,
+    /// };
+    ///
+    /// struct NoWipRule {
+    ///     opts: NoOpts,
+    /// }
+    ///
+    /// impl Rule for NoWipRule {
+    ///     fn name(&self) -> &'static str {
+    ///         "no-wip"
+    ///     }
+    ///
+    ///     fn severity(&self) -> &Severity {
+    ///         &self.opts.0
+    ///     }
+    ///
+    ///     fn run(&self, commit: &Commit) -> Option<miette::Report> {
+    ///         commit
+    ///             .subject
+    ///             .to_string()
+    ///             .contains("WIP")
+    ///             .then(|| miette::miette!(severity = miette::Severity::Error, "Work in progress"))
+    ///     }
+    /// }
+    ///
+    /// let mut registry = RuleRegistry::empty();
+    /// registry.register("no-wip", |opts| Box::new(NoWipRule { opts }));
+    ///
+    /// let config = RulesConfig::from_toml(r#"
+    ///     [rules]
+    ///     no-wip = ["error", "never"]
+    /// "#).unwrap();
+    /// let linter = Linter::from_config_with_registry(config, &registry).unwrap();
+    /// assert!(linter.lint("feat: WIP").has_errors());
+    /// assert!(!linter.lint("feat: add login").has_errors());
+    /// ```
+    pub fn empty() -> Self {
+        RuleRegistry {
+            factories: BTreeMap::new(),
+        }
+    }
+
+    /// Register a rule under the given name, replacing a rule with the same name.
+    /// The options of the rule are deserialized from the config value of that name.
+    pub fn register<O, F>(&mut self, name: impl Into<String>, create: F) -> &mut Self
+    where
+        O: DeserializeOwned + 'static,
+        F: Fn(O) -> Box<dyn Rule> + 'static,
+    {
+        self.factories.insert(
+            name.into(),
+            Box::new(move |value: Value| Ok(create(value.try_deserialize::<O>()?))),
+        );
+        self
+    }
+
+    /// Names of all registered rules
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.factories.keys().map(String::as_str)
+    }
+
+    /// Check if a rule with the given name is registered
+    pub fn contains(&self, name: &str) -> bool {
+        self.factories.contains_key(name)
+    }
+
+    /// Create the rule with the given name from its options
+    pub fn create(&self, name: &str, value: Value) -> Result<Box<dyn Rule>, Report> {
+        let factory = self.factories.get(name).ok_or_else(|| {
+            miette!(
+                code = "config",
+                help = "run `commitguard rules` to see all available rules",
+                "Unknown rule `{}`",
+                name
+            )
+        })?;
+
+        factory(value).map_err(|e| invalid_options(name, e))
+    }
+
+    /// Create all rules which are configured in the config and the rules of all plugins.
+    /// Plugins which are not configured in the `[rules]` table are run with the severity `error`.
+    pub fn create_rules(&self, config: RulesConfig) -> Result<Vec<Box<dyn Rule>>, Report> {
+        let RulesConfig {
+            mut rules,
+            plugins,
+            base_dir,
+            ..
+        } = config;
+
+        let mut created = vec![];
+        for (name, plugin) in &plugins {
+            created.push(plugins::create_rule(
+                name,
+                plugin,
+                rules.remove(name),
+                base_dir.as_deref(),
+            )?);
+        }
+        for (name, value) in rules {
+            if !self.contains(&name) && has_default_options(&name, &value) {
+                continue;
+            }
+            created.push(self.create(&name, value)?);
+        }
+
+        Ok(created)
+    }
+
+    /// Create the rules which replace the configured rules for commits of a type (`[rules.when.type.<type>]`).
+    /// The options of an override are merged into the configured options of the rule,
+    /// so `scope-empty = ["off"]` only changes the severity.
+    pub fn create_type_overrides(&self, config: &RulesConfig) -> Result<TypeOverrides, Report> {
+        let mut overrides = TypeOverrides::new();
+        for (commit_type, rules) in &config.when.types {
+            let mut created = vec![];
+            for (name, value) in rules {
+                let value = match config.rules.get(name) {
+                    Some(options) => merge_options(options, value.clone()),
+                    None => value.clone(),
+                };
+                created.push(match config.plugins.get(name) {
+                    Some(plugin) => {
+                        plugins::create_rule(name, plugin, Some(value), config.base_dir.as_deref())?
+                    }
+                    None => self.create(name, value)?,
+                });
+            }
+            overrides.insert(commit_type.clone(), created);
+        }
+
+        Ok(overrides)
+    }
+}
+
+/// Check if the options of a built-in rule are its defaults, which every config contains
+fn has_default_options(name: &str, value: &Value) -> bool {
+    find_rule_meta(name).is_some_and(|meta| {
+        value.clone().try_deserialize::<serde_json::Value>().ok()
+            == serde_json::from_str(meta.default).ok()
+    })
+}
+
+/// Report for options which don't fit the rule
+pub(crate) fn invalid_options(name: &str, error: config::ConfigError) -> Report {
+    miette!(
+        code = "config",
+        help = format!("run `commitguard explain {}` to see its options", name),
+        "Invalid options for rule `{}`: {}",
+        name,
+        error
+    )
+}
+
+impl Default for RuleRegistry {
+    /// Registry with all built-in rules
+    fn default() -> Self {
+        let mut registry = RuleRegistry::empty();
+        registry
+            .register(body_empty::META.name, |opts| {
+                Box::new(body_empty::BodyEmptyRule { opts })
+            })
+            .register(body_leading_blank::META.name, |opts| {
+                Box::new(body_leading_blank::BodyLeadingBlankRule { opts })
+            })
+            .register(body_pattern::META.name, |opts| {
+                Box::new(body_pattern::BodyPatternRule { opts })
+            })
+            .register(co_authored_by::META.name, |opts| {
+                Box::new(co_authored_by::CoAuthoredByRule { opts })
+            })
+            .register(footer_leading_blank::META.name, |opts| {
+                Box::new(footer_leading_blank::FooterLeadingBlankRule { opts })
+            })
+            .register(footer_pattern::META.name, |opts| {
+                Box::new(footer_pattern::FooterPatternRule { opts })
+            })
+            .register(header_emoji::META.name, |opts| {
+                Box::new(header_emoji::HeaderEmojiRule { opts })
+            })
+            .register(header_pattern::META.name, |opts| {
+                Box::new(header_pattern::HeaderPatternRule { opts })
+            })
+            .register(header_trim::META.name, |opts| {
+                Box::new(header_trim::HeaderTrimRule { opts })
+            })
+            .register(references_empty::META.name, |opts| {
+                Box::new(references_empty::ReferencesEmptyRule { opts })
+            })
+            .register(scope_case::META.name, |opts| {
+                Box::new(scope_case::ScopeCaseRule { opts })
+            })
+            .register(scope_empty::META.name, |opts| {
+                Box::new(scope_empty::ScopeEmptyRule { opts })
+            })
+            .register(scope_enum::META.name, |opts| {
+                Box::new(scope_enum::ScopeEnumRule { opts })
+            })
+            .register(scope_matches_paths::META.name, |opts| {
+                Box::new(scope_matches_paths::ScopeMatchesPathsRule { opts })
+            })
+            .register(scope_max_length::META.name, |opts| {
+                Box::new(scope_max_length::ScopeMaxLengthRule { opts })
+            })
+            .register(signed_off_by::META.name, |opts| {
+                Box::new(signed_off_by::SignedOffByRule { opts })
+            })
+            .register(subject_full_stop::META.name, |opts| {
+                Box::new(subject_full_stop::SubjectFullStopRule { opts })
+            })
+            .register(subject_pattern::META.name, |opts| {
+                Box::new(subject_pattern::SubjectPatternRule { opts })
+            })
+            .register(trailer_exists::META.name, |opts| {
+                Box::new(trailer_exists::TrailerExistsRule { opts })
+            })
+            .register(type_case::META.name, |opts| {
+                Box::new(type_case::TypeCaseRule { opts })
+            })
+            .register(type_enum::META.name, |opts| {
+                Box::new(type_enum::TypeEnumRule { opts })
+            });
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::{parse_commit_loose, ParserOptions},
+        rules::{find_rule_meta, LintContext, Severity, RULES},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_default_registry_contains_all_rules() {
+        let registry = RuleRegistry::default();
+        for meta in RULES.iter() {
+            assert!(registry.contains(meta.name), "{}", meta.name);
+        }
+        assert_eq!(registry.names().count(), RULES.len());
+    }
+
+    #[test]
+    fn test_rule_examples() {
+        // rules which are off by default are turned on, `scope-matches-paths` gets the packages of its examples
+        // and `type-enum` the types of the `conventional` preset
+        let rules: String = RULES
+            .iter()
+            .map(|meta| match meta.name {
+                "type-enum" => format!(
+                    r#"{} = ["error", "always", ["build", "chore", "ci", "docs", "feat", "fix", "perf", "refactor", "revert", "style", "test"]]"#,
+                    meta.name
+                ),
+                "scope-matches-paths" => {
+                    format!(
+                        r#"{} = ["error", {{ api = ["packages/api/**"] }}]"#,
+                        meta.name
+                    )
+                }
+                name => format!(
+                    "{} = {}",
+                    name,
+                    meta.default.replacen(r#"["off""#, r#"["error""#, 1)
+                ),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let config = RulesConfig::from_toml(&format!("[rules]\n{}", rules)).unwrap();
+        let context = LintContext {
+            changed_files: Some(vec![String::from("packages/api/src/lib.rs")]),
+            ..Default::default()
+        };
+
+        for rule in RuleRegistry::default().create_rules(config).unwrap() {
+            let meta = find_rule_meta(rule.name()).unwrap();
+            assert_ne!(rule.severity(), &Severity::Off, "{}", meta.name);
+
+            // a body without a leading blank line can only be parsed for fixing it
+            let parse = |example| parse_commit_loose(example, &ParserOptions::default()).unwrap();
+            for example in meta.good {
+                assert!(
+                    rule.run_with_context(&parse(example), &context).is_none(),
+                    "{} should be valid for {}",
+                    example,
+                    meta.name
+                );
+            }
+            for example in meta.bad {
+                assert!(
+                    rule.run_with_context(&parse(example), &context).is_some(),
+                    "{} should be invalid for {}",
+                    example,
+                    meta.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_create_rules() {
+        let config = RulesConfig::from_toml(
+            r#"
+            [rules]
+            type-enum = ["warning", "always", ["feat"]]
+            "#,
+        )
+        .unwrap();
+        let rules = RuleRegistry::default().create_rules(config).unwrap();
+
+        assert_eq!(rules.len(), RULES.len());
+        let type_enum = rules
+            .iter()
+            .find(|rule| rule.name() == "type-enum")
+            .unwrap();
+        assert_eq!(type_enum.severity(), &Severity::Warning);
+    }
+
+    #[test]
+    fn test_create_type_overrides() {
+        let config = RulesConfig::from_toml(
+            r#"
+            [rules]
+            scope-enum = ["error", "always", ["api"]]
+
+            [rules.when.type.docs]
+            scope-empty = ["off"]
+            scope-enum = ["warning"]
+            "#,
+        )
+        .unwrap();
+        let registry = RuleRegistry::default();
+        let overrides = registry.create_type_overrides(&config).unwrap();
+
+        let docs = &overrides["docs"];
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[0].name(), "scope-empty");
+        assert_eq!(docs[0].severity(), &Severity::Off);
+        assert_eq!(docs[1].severity(), &Severity::Warning);
+
+        // `when` is not a rule
+        assert_eq!(registry.create_rules(config).unwrap().len(), RULES.len());
+    }
+
+    #[test]
+    fn test_invalid_type_overrides() {
+        let config = RulesConfig::from_toml(
+            r#"
+            [rules.when.type.docs]
+            scope-empyt = ["off"]
+            "#,
+        )
+        .unwrap();
+        let report = RuleRegistry::default()
+            .create_type_overrides(&config)
+            .err()
+            .unwrap();
+
+        assert_eq!(report.to_string(), "Unknown rule `scope-empyt`");
+    }
+
+    #[test]
+    fn test_registry_without_built_in_rules() {
+        let config = RulesConfig::from_toml("").unwrap();
+        assert!(RuleRegistry::empty()
+            .create_rules(config)
+            .unwrap()
+            .is_empty());
+
+        // a built-in rule which is configured has to be registered
+        let config = RulesConfig::from_toml(
+            r#"
+            [rules]
+            type-enum = ["error", "always", ["feat"]]
+            "#,
+        )
+        .unwrap();
+        let report = RuleRegistry::empty().create_rules(config).err().unwrap();
+
+        assert_eq!(report.to_string(), "Unknown rule `type-enum`");
+    }
+
+    #[test]
+    fn test_unknown_rule() {
+        let config = RulesConfig::from_toml(
+            r#"
+            [rules]
+            type-enmu = ["error", "always", ["feat"]]
+            "#,
+        )
+        .unwrap();
+        let report = RuleRegistry::default().create_rules(config).err().unwrap();
+
+        assert_eq!(report.to_string(), "Unknown rule `type-enmu`");
+    }
+
+    #[test]
+    fn test_invalid_options() {
+        let config = RulesConfig::from_toml(
+            r#"
+            [rules]
+            scope-max-length = ["error", "always"]
+            "#,
+        )
+        .unwrap();
+        let report = RuleRegistry::default().create_rules(config).err().unwrap();
+
+        assert!(report
+            .to_string()
+            .starts_with("Invalid options for rule `scope-max-length`"));
+    }
+}
@@ -0,0 +1,198 @@
+use crate::parser::Commit;
+
+use super::{rule_url, Condition, Fix, NoOpts, Rule, RuleMeta, Severity};
+use miette::{miette, LabeledSpan, Report};
+
+pub const META: RuleMeta = RuleMeta {
+    name: "body-leading-blank",
+    description:
+        "Body must (`always`) or must not (`never`) be separated from the header by a blank line",
+    options: "[severity, condition]",
+    default: r#"["off", "always"]"#,
+    good: &["feat: add feature\n\nsome body"],
+    bad: &["feat: add feature\nsome body"],
+};
+
+pub struct BodyLeadingBlankRule {
+    pub opts: NoOpts,
+}
+
+impl Rule for BodyLeadingBlankRule {
+    fn name(&self) -> &'static str {
+        META.name
+    }
+
+    fn severity(&self) -> &Severity {
+        &self.opts.0
+    }
+
+    fn run(&self, commit: &Commit) -> Option<Report> {
+        let severity = &self.opts.0;
+        let condition = &self.opts.1;
+
+        if severity == &Severity::Off {
+            return None;
+        }
+
+        if let Some(body) = &commit.body {
+            let newlines = commit.raw[commit.header.end()..body.start()]
+                .matches('\n')
+                .count();
+            let has_blank_line = newlines >= 2;
+            let is_valid = match condition {
+                Condition::Never => !has_blank_line,
+                Condition::Always => has_blank_line,
+            };
+
+            if !is_valid {
+                return Some(
+                    miette!(
+                        severity = match severity {
+                            Severity::Warning => miette::Severity::Warning,
+                            Severity::Error => miette::Severity::Error,
+                            Severity::Off => miette::Severity::Advice,
+                        },
+                        labels = vec![LabeledSpan::at_offset(body.start(), "body starts here")],
+                        help = String::from("body must")
+                            + match condition {
+                                Condition::Never => " not",
+                                Condition::Always => "",
+                            }
+                            + " have a leading blank line",
+                        code = "rule/body-leading-blank",
+                        url = rule_url(META.name),
+                        "{}",
+                        match condition {
+                            Condition::Never => "Body has a leading blank line",
+                            Condition::Always => "Body is missing a leading blank line",
+                        },
+                    )
+                    .with_source_code(commit.raw.clone()),
+                );
+            }
+        }
+
+        None
+    }
+
+    fn fix(&self, commit: &Commit) -> Option<Vec<Fix>> {
+        match self.opts.1 {
+            // keep the line break after the header and remove the blank lines
+            Condition::Never => {
+                let body = commit.body.as_ref()?;
+                let line_break = match commit.raw[commit.header.end()..].starts_with("\r\n") {
+                    true => 2,
+                    false => 1,
+                };
+                Some(vec![Fix::delete(
+                    commit.header.end() + line_break,
+                    body.start(),
+                )])
+            }
+            Condition::Always => Some(vec![Fix::insert(commit.header.end(), "\n")]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        config::RulesConfig,
+        parser::{parse_commit, parse_commit_loose, ParserOptions},
+        rules::LintResult,
+        Linter,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_always_condition() {
+        let rule = BodyLeadingBlankRule {
+            opts: NoOpts(Severity::Error, Condition::Always),
+        };
+
+        let commit = parse_commit("feat: add feature\n\nsome body").unwrap();
+        assert!(rule.run(&commit).is_none());
+
+        // only the fixes parse a body without a leading blank line
+        let commit =
+            parse_commit_loose("feat: add feature\nsome body", &ParserOptions::default()).unwrap();
+        assert!(rule.run(&commit).is_some());
+        assert_eq!(rule.fix(&commit), Some(vec![Fix::insert(17, "\n")]));
+
+        // no body, nothing to check
+        let commit = parse_commit("feat: add feature").unwrap();
+        assert!(rule.run(&commit).is_none());
+    }
+
+    #[test]
+    fn test_never_condition() {
+        let rule = BodyLeadingBlankRule {
+            opts: NoOpts(Severity::Error, Condition::Never),
+        };
+
+        let commit = parse_commit("feat: add feature\n\nsome body").unwrap();
+        assert!(rule.run(&commit).is_some());
+        assert_eq!(rule.fix(&commit), Some(vec![Fix::delete(18, 19)]));
+
+        // only the fixes parse a body without a leading blank line
+        let commit =
+            parse_commit_loose("feat: add feature\nsome body", &ParserOptions::default()).unwrap();
+        assert!(rule.run(&commit).is_none());
+    }
+
+    fn linter(condition: &str) -> Linter {
+        let config = RulesConfig::from_toml(&format!(
+            "[rules]\nbody-leading-blank = [\"error\", \"{}\"]",
+            condition
+        ))
+        .unwrap();
+
+        Linter::from_config(config).unwrap()
+    }
+
+    fn codes(result: &LintResult) -> Vec<String> {
+        result
+            .errors()
+            .into_iter()
+            .flatten()
+            .map(|report| report.code().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_lint_and_fix_always() {
+        let linter = linter("always");
+
+        let message = "feat(foo): add feature\nsome body";
+        assert_eq!(codes(&linter.lint(message)), ["rule/body-leading-blank"]);
+        let fixed = linter.fix(message);
+        assert_eq!(fixed, "feat(foo): add feature\n\nsome body");
+        assert!(codes(&linter.lint(&fixed)).is_empty());
+    }
+
+    #[test]
+    fn test_lint_and_fix_never() {
+        let linter = linter("never");
+
+        let message = "feat(foo): add feature\n\nsome body";
+        assert_eq!(codes(&linter.lint(message)), ["rule/body-leading-blank"]);
+        let fixed = linter.fix(message);
+        assert_eq!(fixed, "feat(foo): add feature\nsome body");
+        assert!(codes(&linter.lint(&fixed)).is_empty());
+    }
+
+    #[test]
+    fn test_parse_error_when_off() {
+        let linter = linter("always");
+        let config = RulesConfig::from_toml("[rules]\nbody-leading-blank = [\"off\", \"always\"]");
+        let off = Linter::from_config(config.unwrap()).unwrap();
+
+        // without the rule, a body directly after the header can't be parsed
+        assert_eq!(
+            codes(&off.lint("feat(foo): add feature\nsome body")),
+            ["parser"]
+        );
+        assert!(linter.parse("feat(foo): add feature\nsome body").is_ok());
+    }
+}
@@ -0,0 +1,85 @@
+use crate::parser::Commit;
+
+use super::{check_pattern, PatternOpts, Rule, RuleMeta, Severity};
+use miette::Report;
+
+pub const META: RuleMeta = RuleMeta {
+    name: "body-pattern",
+    description: "Body must (`always`) or must not (`never`) match the given regex",
+    options: "[severity, condition, regex]",
+    default: r#"["off", "never", "TODO"]"#,
+    good: &["feat: add feature\n\nexplain why the feature is needed"],
+    bad: &["feat: add feature\n\nTODO: explain why"],
+};
+
+pub struct BodyPatternRule {
+    pub opts: PatternOpts,
+}
+
+impl Rule for BodyPatternRule {
+    fn name(&self) -> &'static str {
+        META.name
+    }
+
+    fn severity(&self) -> &Severity {
+        &self.opts.0
+    }
+
+    fn run(&self, commit: &Commit) -> Option<Report> {
+        check_pattern(META.name, "body", commit.body.as_ref(), &self.opts, commit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use regex::Regex;
+
+    use crate::{parser::CommitSpan, rules::Condition};
+
+    use super::*;
+
+    #[test]
+    fn test_body_pattern() {
+        let rule = BodyPatternRule {
+            opts: PatternOpts(
+                Severity::Error,
+                Condition::Never,
+                Regex::new("TODO").unwrap(),
+            ),
+        };
+
+        // a missing body doesn't contain the forbidden pattern
+        assert!(rule.run(&Commit::default()).is_none());
+
+        let commit = Commit {
+            body: Some(CommitSpan::new("explain why", 19, 30)),
+            ..Default::default()
+        };
+        assert!(rule.run(&commit).is_none());
+
+        let commit = Commit {
+            body: Some(CommitSpan::new("TODO: explain why", 19, 36)),
+            ..Default::default()
+        };
+        let report = rule.run(&commit).unwrap();
+        assert_eq!(
+            report.help().unwrap().to_string(),
+            "body must not match `TODO`"
+        );
+    }
+
+    #[test]
+    fn test_missing_body() {
+        let rule = BodyPatternRule {
+            opts: PatternOpts(
+                Severity::Error,
+                Condition::Always,
+                Regex::new("because").unwrap(),
+            ),
+        };
+
+        let report = rule.run(&Commit::default()).unwrap();
+        assert_eq!(report.to_string(), "Body is missing");
+        assert_eq!(report.labels().map_or(0, |labels| labels.count()), 0);
+    }
+}
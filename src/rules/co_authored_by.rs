@@ -0,0 +1,253 @@
+use crate::parser::Commit;
+
+use super::{rule_url, AllowlistOpts, Rule, RuleMeta, Severity};
+use miette::{miette, LabeledSpan, Report};
+
+pub const META: RuleMeta = RuleMeta {
+    name: "co-authored-by",
+    description: "Co-authored-by trailers must look like `Name <email>` and must not be duplicated. If domains are given, the emails must belong to one of them.",
+    options: "[severity, [domains]]",
+    default: r#"["off", []]"#,
+    good: &["feat: add feature\n\nCo-authored-by: Jane Doe <jane@example.com>"],
+    bad: &["feat: add feature\n\nCo-authored-by: Jane Doe jane@example.com"],
+};
+
+const TOKEN: &str = "co-authored-by:";
+
+/// Split the value of a trailer into the name and the email of `Name <email>`
+fn parse_co_author(value: &str) -> Option<(&str, &str)> {
+    let (name, email) = value.strip_suffix('>')?.rsplit_once(" <")?;
+    let (local, domain) = email.split_once('@')?;
+
+    let is_valid = !name.trim().is_empty()
+        && !name.contains(['<', '>'])
+        && !local.is_empty()
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && !email.contains(|c: char| c.is_whitespace() || c == '<' || c == '>')
+        && !domain.contains('@');
+
+    is_valid.then_some((name.trim(), email))
+}
+
+pub struct CoAuthoredByRule {
+    pub opts: AllowlistOpts,
+}
+
+impl Rule for CoAuthoredByRule {
+    fn name(&self) -> &'static str {
+        META.name
+    }
+
+    fn severity(&self) -> &Severity {
+        &self.opts.0
+    }
+
+    fn run(&self, commit: &Commit) -> Option<Report> {
+        let severity = &self.opts.0;
+        let domains = &self.opts.1;
+
+        if severity == &Severity::Off {
+            return None;
+        }
+
+        let mut labels = vec![];
+        let mut emails: Vec<String> = vec![];
+        // lines which only start like a co-author (e.g. `Co-authored by: ...`) are checked as well
+        for line in commit.last_paragraph_lines() {
+            let text = line.as_str();
+            let starts_with = |prefix: &str| {
+                text.get(..prefix.len())
+                    .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+            };
+            if !starts_with("co-authored") {
+                continue;
+            }
+
+            let span = line.start()..line.end();
+            let co_author = starts_with(TOKEN)
+                .then(|| &text[TOKEN.len()..])
+                .and_then(|value| value.strip_prefix(' '))
+                .and_then(parse_co_author);
+            let Some((_, email)) = co_author else {
+                labels.push(LabeledSpan::at(span, "expected `Name <email>`"));
+                continue;
+            };
+
+            let email = email.to_lowercase();
+            let domain = &email[email.rfind('@').unwrap() + 1..];
+            if emails.contains(&email) {
+                labels.push(LabeledSpan::at(span, "duplicate co-author"));
+            } else if !domains.is_empty()
+                && !domains
+                    .iter()
+                    .any(|allowed| allowed.eq_ignore_ascii_case(domain))
+            {
+                labels.push(LabeledSpan::at(span, "domain not allowed"));
+            }
+            emails.push(email);
+        }
+
+        if labels.is_empty() {
+            return None;
+        }
+
+        Some(
+            miette!(
+                severity = match severity {
+                    Severity::Warning => miette::Severity::Warning,
+                    Severity::Error => miette::Severity::Error,
+                    Severity::Off => miette::Severity::Advice,
+                },
+                labels = labels,
+                help = String::from(
+                    "co-authors must be credited once as `Co-authored-by: Name <email>`"
+                ) + match domains.is_empty() {
+                    true => String::new(),
+                    false => format!(" with an email of {}", domains.join(", ")),
+                }
+                .as_str(),
+                code = "rule/co-authored-by",
+                url = rule_url(META.name),
+                "Co-authored-by trailers are invalid",
+            )
+            .with_source_code(commit.raw.clone()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_commit;
+
+    use super::*;
+
+    fn rule(domains: &[&str]) -> CoAuthoredByRule {
+        CoAuthoredByRule {
+            opts: AllowlistOpts(
+                Severity::Error,
+                domains.iter().map(|domain| domain.to_string()).collect(),
+            ),
+        }
+    }
+
+    fn label_texts(report: &Report) -> Vec<String> {
+        report
+            .labels()
+            .unwrap()
+            .map(|label| label.label().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_co_author() {
+        assert_eq!(
+            parse_co_author("Jane Doe <jane@example.com>"),
+            Some(("Jane Doe", "jane@example.com"))
+        );
+        assert_eq!(parse_co_author("Jane Doe jane@example.com"), None);
+        assert_eq!(parse_co_author("<jane@example.com>"), None);
+        assert_eq!(parse_co_author("Jane Doe <jane>"), None);
+        assert_eq!(parse_co_author("Jane Doe <jane@localhost>"), None);
+        assert_eq!(parse_co_author("Jane Doe <jane doe@example.com>"), None);
+    }
+
+    #[test]
+    fn test_valid_co_authors() {
+        let commit = parse_commit(
+            "feat: add feature\n\nsome body\n\nCo-authored-by: Jane Doe <jane@example.com>\nco-authored-by: John Doe <john@example.org>",
+        )
+        .unwrap();
+        assert!(rule(&[]).run(&commit).is_none());
+
+        let commit = parse_commit("feat: add feature").unwrap();
+        assert!(rule(&[]).run(&commit).is_none());
+
+        // only the trailers in the last paragraph are checked
+        let commit = parse_commit(
+            "feat: add feature\n\nCo-authored-by: is explained in the docs\n\nCloses #1",
+        )
+        .unwrap();
+        assert!(rule(&[]).run(&commit).is_none());
+    }
+
+    #[test]
+    fn test_invalid_syntax() {
+        let commit = parse_commit(
+            "feat: add feature\n\nCo-authored-by: Jane Doe\nCo-authored-by: <john@example.com>",
+        )
+        .unwrap();
+        let report = rule(&[]).run(&commit).unwrap();
+
+        assert_eq!(
+            label_texts(&report),
+            ["expected `Name <email>`", "expected `Name <email>`"]
+        );
+        let labels: Vec<_> = report.labels().unwrap().collect();
+        assert_eq!(labels[0].offset(), 19);
+        assert_eq!(labels[0].len(), 24);
+        assert_eq!(labels[1].offset(), 44);
+    }
+
+    #[test]
+    fn test_malformed_trailers() {
+        let commit = parse_commit(
+            "feat: add feature\n\nCo-authored-by:Jane <jane@x.io>\nCo-authored by: Jane <jane@x.io>",
+        )
+        .unwrap();
+        let report = rule(&[]).run(&commit).unwrap();
+
+        assert_eq!(
+            label_texts(&report),
+            ["expected `Name <email>`", "expected `Name <email>`"]
+        );
+        let labels: Vec<_> = report.labels().unwrap().collect();
+        assert_eq!((labels[0].offset(), labels[0].len()), (19, 31));
+        assert_eq!((labels[1].offset(), labels[1].len()), (51, 32));
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let commit = parse_commit(
+            "feat: add feature\n\nCo-authored-by: Jane Doe <jane@example.com>\nCo-authored-by: Jane <JANE@example.com>",
+        )
+        .unwrap();
+        let report = rule(&[]).run(&commit).unwrap();
+
+        assert_eq!(label_texts(&report), ["duplicate co-author"]);
+    }
+
+    #[test]
+    fn test_allowed_domains() {
+        let commit = parse_commit(
+            "feat: add feature\n\nCo-authored-by: Jane Doe <jane@example.com>\nCo-authored-by: John Doe <john@gmail.com>",
+        )
+        .unwrap();
+
+        let report = rule(&["example.com"]).run(&commit).unwrap();
+        assert_eq!(label_texts(&report), ["domain not allowed"]);
+        assert!(report.help().unwrap().to_string().ends_with("example.com"));
+
+        assert!(rule(&["example.com", "gmail.com"]).run(&commit).is_none());
+    }
+
+    #[test]
+    fn test_options() {
+        let opts: AllowlistOpts = serde_json::from_str(r#"["warning"]"#).unwrap();
+        assert!(opts.1.is_empty());
+
+        let opts: AllowlistOpts = serde_json::from_str(r#"["error", ["example.com"]]"#).unwrap();
+        assert_eq!(opts.1, ["example.com"]);
+    }
+
+    #[test]
+    fn test_severity_off() {
+        let rule = CoAuthoredByRule {
+            opts: AllowlistOpts(Severity::Off, vec![]),
+        };
+
+        let commit = parse_commit("feat: add feature\n\nCo-authored-by: Jane Doe").unwrap();
+        assert!(rule.run(&commit).is_none());
+    }
+}
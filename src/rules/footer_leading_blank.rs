@@ -0,0 +1,208 @@
+use crate::parser::{is_footer_line, Commit};
+
+use super::{rule_url, Condition, Fix, NoOpts, Rule, RuleMeta, Severity};
+use miette::{miette, LabeledSpan, Report};
+
+pub const META: RuleMeta = RuleMeta {
+    name: "footer-leading-blank",
+    description:
+        "Footer must (`always`) or must not (`never`) be separated from the body by a blank line",
+    options: "[severity, condition]",
+    default: r#"["off", "always"]"#,
+    good: &["feat: add feature\n\nsome body\n\nCloses #1"],
+    bad: &["feat: add feature\n\nsome body\nCloses #1"],
+};
+
+pub struct FooterLeadingBlankRule {
+    pub opts: NoOpts,
+}
+
+/// Lines of the last paragraph of the message with their offsets: the footer, or the body if there is no footer
+fn last_paragraph<'a>(commit: &'a Commit) -> Vec<(usize, &'a str)> {
+    let Some(span) = commit.footer.as_ref().or(commit.body.as_ref()) else {
+        return vec![];
+    };
+    let text = span.as_str().trim_end();
+    let paragraph_start = text.rfind("\n\n").map(|pos| pos + 2).unwrap_or(0);
+
+    let mut start = span.start() + paragraph_start;
+    text[paragraph_start..]
+        .split('\n')
+        .map(|line| {
+            let line_start = start;
+            start += line.len() + 1;
+            (line_start, line.trim_end())
+        })
+        .collect()
+}
+
+/// Offset of the first line of a footer which directly follows other text, without a blank line in front of it.
+/// The footer lines are the last lines of the last paragraph, which all look like a footer (e.g. `Closes #1`).
+fn footer_without_blank_line(commit: &Commit) -> Option<usize> {
+    let lines = last_paragraph(commit);
+
+    // the first line of the paragraph follows a blank line already
+    let footer_len = lines
+        .iter()
+        .skip(1)
+        .rev()
+        .take_while(|(_, line)| is_footer_line(line))
+        .count();
+
+    match footer_len {
+        0 => None,
+        len => Some(lines[lines.len() - len].0),
+    }
+}
+
+/// Offset of a footer which is separated from the text in front of it by a blank line,
+/// that is a last paragraph which only consists of footer lines
+fn footer_with_blank_line(commit: &Commit) -> Option<usize> {
+    let lines = last_paragraph(commit);
+    let (start, _) = lines.first()?;
+    let is_footer = lines.iter().all(|(_, line)| is_footer_line(line));
+
+    // a footer which is the only paragraph after the header has no text in front of it
+    let follows_text = commit
+        .body
+        .as_ref()
+        .is_some_and(|body| body.start() < *start);
+
+    (is_footer && follows_text).then_some(*start)
+}
+
+impl Rule for FooterLeadingBlankRule {
+    fn name(&self) -> &'static str {
+        META.name
+    }
+
+    fn severity(&self) -> &Severity {
+        &self.opts.0
+    }
+
+    fn run(&self, commit: &Commit) -> Option<Report> {
+        let severity = &self.opts.0;
+        let condition = &self.opts.1;
+
+        if severity == &Severity::Off {
+            return None;
+        }
+
+        let footer_start = match condition {
+            Condition::Always => footer_without_blank_line(commit)?,
+            Condition::Never => footer_with_blank_line(commit)?,
+        };
+
+        Some(
+            miette!(
+                severity = match severity {
+                    Severity::Warning => miette::Severity::Warning,
+                    Severity::Error => miette::Severity::Error,
+                    Severity::Off => miette::Severity::Advice,
+                },
+                labels = vec![LabeledSpan::at_offset(footer_start, "footer starts here")],
+                help = String::from("footer must")
+                    + match condition {
+                        Condition::Never => " not",
+                        Condition::Always => "",
+                    }
+                    + " have a leading blank line",
+                code = "rule/footer-leading-blank",
+                url = rule_url(META.name),
+                "{}",
+                match condition {
+                    Condition::Never => "Footer has a leading blank line",
+                    Condition::Always => "Footer is missing a leading blank line",
+                },
+            )
+            .with_source_code(commit.raw.clone()),
+        )
+    }
+
+    fn fix(&self, commit: &Commit) -> Option<Vec<Fix>> {
+        match self.opts.1 {
+            Condition::Never => None,
+            Condition::Always => {
+                footer_without_blank_line(commit).map(|start| vec![Fix::insert(start, "\n")])
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_commit;
+
+    use super::*;
+
+    #[test]
+    fn test_always_condition() {
+        let rule = FooterLeadingBlankRule {
+            opts: NoOpts(Severity::Error, Condition::Always),
+        };
+
+        let commit = parse_commit("feat: add feature\n\nsome body\n\nCloses #1").unwrap();
+        assert!(rule.run(&commit).is_none());
+
+        let commit = parse_commit("feat: add feature\n\nsome body\nCloses #1").unwrap();
+        let report = rule.run(&commit).unwrap();
+        let labels: Vec<_> = report.labels().unwrap().collect();
+        assert_eq!(labels[0].offset(), 29);
+        assert_eq!(rule.fix(&commit), Some(vec![Fix::insert(29, "\n")]));
+
+        let commit = parse_commit(
+            "feat: add feature\n\nsome body\nRefs: #2\nSigned-off-by: Jane Doe <jane@example.com>",
+        )
+        .unwrap();
+        assert_eq!(rule.fix(&commit), Some(vec![Fix::insert(29, "\n")]));
+
+        // lines which look like a footer in the middle of the body are no footer
+        let commit =
+            parse_commit("feat: add feature\n\nsome body\nNote: read this\nmore body").unwrap();
+        assert!(rule.run(&commit).is_none());
+
+        // no body, nothing to check
+        let commit = parse_commit("feat: add feature").unwrap();
+        assert!(rule.run(&commit).is_none());
+    }
+
+    #[test]
+    fn test_multiple_paragraphs() {
+        let rule = FooterLeadingBlankRule {
+            opts: NoOpts(Severity::Error, Condition::Always),
+        };
+
+        // the last paragraph is parsed as the footer
+        let commit = parse_commit("feat(foo): add x\n\nbody one\n\nbody two\nCloses #1").unwrap();
+        assert!(commit.footer.is_some());
+        let report = rule.run(&commit).unwrap();
+        let labels: Vec<_> = report.labels().unwrap().collect();
+        assert_eq!(labels[0].offset(), 37);
+        assert_eq!(rule.fix(&commit), Some(vec![Fix::insert(37, "\n")]));
+
+        let commit = parse_commit("feat(foo): add x\n\nbody one\n\nbody two\n\nCloses #1").unwrap();
+        assert!(rule.run(&commit).is_none());
+    }
+
+    #[test]
+    fn test_never_condition() {
+        let rule = FooterLeadingBlankRule {
+            opts: NoOpts(Severity::Error, Condition::Never),
+        };
+
+        let commit = parse_commit("feat: add feature\n\nsome body\n\nCloses #1").unwrap();
+        assert!(rule.run(&commit).is_some());
+        assert_eq!(rule.fix(&commit), None);
+
+        let commit = parse_commit("feat: add feature\n\nsome body\nCloses #1").unwrap();
+        assert!(rule.run(&commit).is_none());
+
+        // a last paragraph of text is no footer
+        let commit = parse_commit("feat: add feature\n\nsome body\n\nmore body").unwrap();
+        assert!(rule.run(&commit).is_none());
+
+        // nothing is in front of a footer which directly follows the header
+        let commit = parse_commit("feat: add feature\n\nCloses #1").unwrap();
+        assert!(rule.run(&commit).is_none());
+    }
+}
@@ -0,0 +1,119 @@
+use crate::parser::{is_footer_line, Commit, CommitSpan};
+
+use super::{check_pattern, PatternOpts, Rule, RuleMeta, Severity};
+use miette::Report;
+
+pub const META: RuleMeta = RuleMeta {
+    name: "footer-pattern",
+    description: "Footer must (`always`) or must not (`never`) match the given regex",
+    options: "[severity, condition, regex]",
+    default: r##"["off", "always", "#[0-9]+"]"##,
+    good: &["feat: add feature\n\nsome body\n\nCloses #123"],
+    bad: &["feat: add feature\n\nsome body\n\nCloses the ticket"],
+};
+
+pub struct FooterPatternRule {
+    pub opts: PatternOpts,
+}
+
+impl Rule for FooterPatternRule {
+    fn name(&self) -> &'static str {
+        META.name
+    }
+
+    fn severity(&self) -> &Severity {
+        &self.opts.0
+    }
+
+    fn run(&self, commit: &Commit) -> Option<Report> {
+        let footer = commit
+            .footer
+            .as_ref()
+            .map(|footer| CommitSpan::new(footer.as_str(), footer.start(), footer.end()));
+
+        check_pattern(
+            META.name,
+            "footer",
+            footer.or_else(|| footer_as_body(commit)).as_ref(),
+            &self.opts,
+            commit,
+        )
+    }
+}
+
+/// A footer without a body in front of it (e.g. `fix: x\n\nCloses #12`) is parsed as the body,
+/// so a body which only consists of footer lines is checked as the footer.
+fn footer_as_body<'a>(commit: &'a Commit) -> Option<CommitSpan<'a>> {
+    let body = commit.body.as_ref()?;
+    let text = body.as_str().trim_end();
+
+    text.lines()
+        .all(|line| is_footer_line(line.trim_end()))
+        .then(|| CommitSpan::new(text, body.start(), body.start() + text.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use regex::Regex;
+
+    use crate::{parser::parse_commit, rules::Condition};
+
+    use super::*;
+
+    #[test]
+    fn test_footer_pattern() {
+        let rule = FooterPatternRule {
+            opts: PatternOpts(
+                Severity::Warning,
+                Condition::Always,
+                Regex::new("#[0-9]+").unwrap(),
+            ),
+        };
+
+        // a missing footer can't match the pattern
+        let report = rule.run(&Commit::default()).unwrap();
+        assert_eq!(report.to_string(), "Footer is missing");
+        assert_eq!(
+            report.help().unwrap().to_string(),
+            "footer must match `#[0-9]+`"
+        );
+
+        let commit = Commit {
+            footer: Some(CommitSpan::new("Closes #123", 30, 41)),
+            ..Default::default()
+        };
+        assert!(rule.run(&commit).is_none());
+
+        let commit = Commit {
+            footer: Some(CommitSpan::new("Closes the ticket", 30, 47)),
+            ..Default::default()
+        };
+        let report = rule.run(&commit).unwrap();
+        assert_eq!(report.to_string(), "Footer doesn't match the pattern");
+        assert_eq!(report.severity(), Some(miette::Severity::Warning));
+    }
+
+    #[test]
+    fn test_footer_without_body() {
+        let rule = FooterPatternRule {
+            opts: PatternOpts(
+                Severity::Error,
+                Condition::Always,
+                Regex::new("#[0-9]+").unwrap(),
+            ),
+        };
+
+        let commit = parse_commit("fix: x\n\nCloses #12").unwrap();
+        assert!(commit.footer.is_none());
+        assert!(rule.run(&commit).is_none());
+
+        let commit = parse_commit("fix: x\n\nCloses the ticket").unwrap();
+        assert_eq!(rule.run(&commit).unwrap().to_string(), "Footer is missing");
+
+        let commit = parse_commit("fix: x\n\nRefs: the ticket").unwrap();
+        let report = rule.run(&commit).unwrap();
+        assert_eq!(report.to_string(), "Footer doesn't match the pattern");
+        let label = report.labels().unwrap().next().unwrap();
+        assert_eq!((label.offset(), label.len()), (8, 16));
+    }
+}
@@ -0,0 +1,208 @@
+use crate::parser::Commit;
+
+use super::{rule_url, Condition, Rule, RuleMeta, Severity, TypeMapOpts};
+use miette::{miette, LabeledSpan, Report};
+
+pub const META: RuleMeta = RuleMeta {
+    name: "header-emoji",
+    description: "Header must (`always`) or must not (`never`) start with an emoji. Emojis can be limited to types, e.g. `{ \"✨\" = [\"feat\"] }`, then every other emoji is not allowed.",
+    options: "[severity, condition, { emoji = [types] }]",
+    default: r#"["off", "always", {}]"#,
+    good: &["✨ feat: add feature", ":bug: fix: fix crash"],
+    bad: &["feat: add feature"],
+};
+
+pub struct HeaderEmojiRule {
+    pub opts: TypeMapOpts,
+}
+
+impl Rule for HeaderEmojiRule {
+    fn name(&self) -> &'static str {
+        META.name
+    }
+
+    fn severity(&self) -> &Severity {
+        &self.opts.0
+    }
+
+    fn run(&self, commit: &Commit) -> Option<Report> {
+        let severity = &self.opts.0;
+        let condition = &self.opts.1;
+        let types_by_emoji = &self.opts.2;
+
+        if severity == &Severity::Off {
+            return None;
+        }
+
+        let (label, help, message) = match (condition, &commit.emoji) {
+            (Condition::Never, None) => return None,
+            (Condition::Never, Some(emoji)) => (
+                LabeledSpan::at(emoji.start()..emoji.end(), "not allowed emoji"),
+                String::from("header must not start with an emoji"),
+                "Header starts with an emoji",
+            ),
+            (Condition::Always, None) => (
+                LabeledSpan::at_offset(commit.header.start(), "missing emoji"),
+                String::from("header must start with an emoji")
+                    + match types_by_emoji.is_empty() {
+                        true => String::new(),
+                        false => format!(
+                            " (one of {})",
+                            types_by_emoji
+                                .keys()
+                                .cloned()
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                    }
+                    .as_str(),
+                "Header doesn't start with an emoji",
+            ),
+            (Condition::Always, Some(_)) if types_by_emoji.is_empty() => return None,
+            (Condition::Always, Some(emoji)) => match types_by_emoji.get(&emoji.to_string()) {
+                None => (
+                    LabeledSpan::at(emoji.start()..emoji.end(), "unknown emoji"),
+                    String::from("emoji must be one of ")
+                        + types_by_emoji
+                            .keys()
+                            .cloned()
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                            .as_str(),
+                    "Emoji not allowed",
+                ),
+                Some(types) if types.contains(&commit.commit_type.to_string()) => return None,
+                Some(types) => (
+                    LabeledSpan::at(
+                        emoji.start()..emoji.end(),
+                        format!("not allowed for `{}`", commit.commit_type),
+                    ),
+                    format!("{} is only allowed for {}", emoji, types.join(", ")),
+                    "Emoji doesn't fit the type",
+                ),
+            },
+        };
+
+        Some(
+            miette!(
+                severity = match severity {
+                    Severity::Warning => miette::Severity::Warning,
+                    Severity::Error => miette::Severity::Error,
+                    Severity::Off => miette::Severity::Advice,
+                },
+                labels = vec![label],
+                help = help,
+                code = "rule/header-emoji",
+                url = rule_url(META.name),
+                "{}",
+                message,
+            )
+            .with_source_code(commit.raw.clone()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::parser::parse_commit;
+
+    use super::*;
+
+    fn rule(condition: Condition, types_by_emoji: &[(&str, &[&str])]) -> HeaderEmojiRule {
+        HeaderEmojiRule {
+            opts: TypeMapOpts(
+                Severity::Error,
+                condition,
+                types_by_emoji
+                    .iter()
+                    .map(|(emoji, types)| {
+                        (
+                            emoji.to_string(),
+                            types.iter().map(|t| t.to_string()).collect(),
+                        )
+                    })
+                    .collect::<BTreeMap<_, _>>(),
+            ),
+        }
+    }
+
+    #[test]
+    fn test_always_condition() {
+        let rule = rule(Condition::Always, &[]);
+
+        let commit = parse_commit("✨ feat: add feature").unwrap();
+        assert!(rule.run(&commit).is_none());
+
+        let commit = parse_commit("feat: add feature").unwrap();
+        assert!(rule.run(&commit).is_some());
+    }
+
+    #[test]
+    fn test_never_condition() {
+        let rule = rule(Condition::Never, &[]);
+
+        let commit = parse_commit("feat: add feature").unwrap();
+        assert!(rule.run(&commit).is_none());
+
+        let commit = parse_commit(":sparkles: feat: add feature").unwrap();
+        let report = rule.run(&commit).unwrap();
+        let labels: Vec<_> = report.labels().unwrap().collect();
+        assert_eq!((labels[0].offset(), labels[0].len()), (0, 10));
+    }
+
+    #[test]
+    fn test_emojis_by_type() {
+        let rule = rule(
+            Condition::Always,
+            &[
+                ("✨", &["feat"]),
+                (":bug:", &["fix"]),
+                ("📝", &["docs", "chore"]),
+            ],
+        );
+
+        let commit = parse_commit("✨ feat: add feature").unwrap();
+        assert!(rule.run(&commit).is_none());
+        let commit = parse_commit("📝 chore: update readme").unwrap();
+        assert!(rule.run(&commit).is_none());
+
+        let commit = parse_commit("✨ fix: fix crash").unwrap();
+        let report = rule.run(&commit).unwrap();
+        assert_eq!(report.to_string(), "Emoji doesn't fit the type");
+        assert_eq!(
+            report.help().unwrap().to_string(),
+            "✨ is only allowed for feat"
+        );
+
+        let commit = parse_commit("🔥 fix: fix crash").unwrap();
+        let report = rule.run(&commit).unwrap();
+        assert_eq!(report.to_string(), "Emoji not allowed");
+    }
+
+    #[test]
+    fn test_options_from_config() {
+        let config = crate::config::RulesConfig::from_toml(
+            r#"
+            [rules]
+            header-emoji = ["error", "always", { "✨" = ["feat"], ":bug:" = ["fix"] }]
+            "#,
+        )
+        .unwrap();
+        let linter = crate::Linter::from_config(config).unwrap();
+
+        assert!(!linter.lint(":bug: fix(foo): fix crash").has_errors());
+        assert!(linter.lint(":bug: feat(foo): add feature").has_errors());
+    }
+
+    #[test]
+    fn test_severity_off() {
+        let rule = HeaderEmojiRule {
+            opts: TypeMapOpts(Severity::Off, Condition::Always, BTreeMap::new()),
+        };
+
+        let commit = parse_commit("feat: add feature").unwrap();
+        assert!(rule.run(&commit).is_none());
+    }
+}
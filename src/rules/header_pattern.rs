@@ -0,0 +1,72 @@
+use crate::parser::Commit;
+
+use super::{check_pattern, PatternOpts, Rule, RuleMeta, Severity};
+use miette::Report;
+
+pub const META: RuleMeta = RuleMeta {
+    name: "header-pattern",
+    description: "Header must (`always`) or must not (`never`) match the given regex",
+    options: "[severity, condition, regex]",
+    default: r#"["off", "never", "(?i)\\bwip\\b"]"#,
+    good: &["feat: add feature"],
+    bad: &["feat: add feature (WIP)"],
+};
+
+pub struct HeaderPatternRule {
+    pub opts: PatternOpts,
+}
+
+impl Rule for HeaderPatternRule {
+    fn name(&self) -> &'static str {
+        META.name
+    }
+
+    fn severity(&self) -> &Severity {
+        &self.opts.0
+    }
+
+    fn run(&self, commit: &Commit) -> Option<Report> {
+        check_pattern(
+            META.name,
+            "header",
+            Some(&commit.header),
+            &self.opts,
+            commit,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use regex::Regex;
+
+    use crate::{parser::CommitSpan, rules::Condition};
+
+    use super::*;
+
+    #[test]
+    fn test_header_pattern() {
+        let rule = HeaderPatternRule {
+            opts: PatternOpts(
+                Severity::Error,
+                Condition::Never,
+                Regex::new(r"(?i)\bwip\b").unwrap(),
+            ),
+        };
+
+        let commit = Commit {
+            header: CommitSpan::new("feat: add feature", 0, 17),
+            ..Default::default()
+        };
+        assert!(rule.run(&commit).is_none());
+
+        let commit = Commit {
+            header: CommitSpan::new("feat: add feature (WIP)", 0, 23),
+            ..Default::default()
+        };
+        let report = rule.run(&commit).unwrap();
+        assert_eq!(report.to_string(), "Header matches a forbidden pattern");
+        let labels: Vec<_> = report.labels().unwrap().collect();
+        assert_eq!((labels[0].offset(), labels[0].len()), (19, 3));
+    }
+}
@@ -0,0 +1,137 @@
+use crate::parser::Commit;
+
+use super::{rule_url, Condition, Fix, NoOpts, Rule, RuleMeta, Severity};
+use miette::{miette, LabeledSpan, Report};
+
+pub const META: RuleMeta = RuleMeta {
+    name: "header-trim",
+    description: "Header must (`always`) or must not (`never`) be free of trailing whitespace",
+    options: "[severity, condition]",
+    default: r#"["off", "always"]"#,
+    good: &["feat: add feature"],
+    bad: &["feat: add feature  "],
+};
+
+pub struct HeaderTrimRule {
+    pub opts: NoOpts,
+}
+
+impl HeaderTrimRule {
+    /// Start of the trailing whitespace of the header
+    fn trimmed_end(commit: &Commit) -> usize {
+        commit.header.start() + commit.header.to_string().trim_end().len()
+    }
+}
+
+impl Rule for HeaderTrimRule {
+    fn name(&self) -> &'static str {
+        META.name
+    }
+
+    fn severity(&self) -> &Severity {
+        &self.opts.0
+    }
+
+    fn run(&self, commit: &Commit) -> Option<Report> {
+        let severity = &self.opts.0;
+        let condition = &self.opts.1;
+
+        if severity == &Severity::Off {
+            return None;
+        }
+
+        let trimmed_end = Self::trimmed_end(commit);
+        let is_trimmed = trimmed_end == commit.header.end();
+        let is_valid = match condition {
+            Condition::Never => !is_trimmed,
+            Condition::Always => is_trimmed,
+        };
+
+        if !is_valid {
+            let labels = match condition {
+                Condition::Never => vec![],
+                Condition::Always => vec![LabeledSpan::at(
+                    trimmed_end..commit.header.end(),
+                    "not allowed whitespace",
+                )],
+            };
+
+            return Some(
+                miette!(
+                    severity = match severity {
+                        Severity::Warning => miette::Severity::Warning,
+                        Severity::Error => miette::Severity::Error,
+                        Severity::Off => miette::Severity::Advice,
+                    },
+                    labels = labels,
+                    help = String::from("header must")
+                        + match condition {
+                            Condition::Never => " not",
+                            Condition::Always => "",
+                        }
+                        + " be trimmed",
+                    code = "rule/header-trim",
+                    url = rule_url(META.name),
+                    "{}",
+                    match condition {
+                        Condition::Never => "Header is trimmed",
+                        Condition::Always => "Header has trailing whitespace",
+                    },
+                )
+                .with_source_code(commit.raw.clone()),
+            );
+        }
+
+        None
+    }
+
+    fn fix(&self, commit: &Commit) -> Option<Vec<Fix>> {
+        match self.opts.1 {
+            Condition::Never => None,
+            Condition::Always => Some(vec![Fix::delete(
+                Self::trimmed_end(commit),
+                commit.header.end(),
+            )]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::CommitSpan;
+
+    use super::*;
+
+    #[test]
+    fn test_always_condition() {
+        let rule = HeaderTrimRule {
+            opts: NoOpts(Severity::Error, Condition::Always),
+        };
+
+        let commit = Commit {
+            header: CommitSpan::new("feat: add feature", 0, 17),
+            ..Default::default()
+        };
+        assert!(rule.run(&commit).is_none());
+
+        let commit = Commit {
+            header: CommitSpan::new("feat: add feature \t", 0, 19),
+            ..Default::default()
+        };
+        assert!(rule.run(&commit).is_some());
+        assert_eq!(rule.fix(&commit), Some(vec![Fix::delete(17, 19)]));
+    }
+
+    #[test]
+    fn test_severity_off() {
+        let rule = HeaderTrimRule {
+            opts: NoOpts(Severity::Off, Condition::Always),
+        };
+
+        let commit = Commit {
+            header: CommitSpan::new("feat: add feature ", 0, 18),
+            ..Default::default()
+        };
+        assert!(rule.run(&commit).is_none());
+    }
+}
@@ -1,21 +1,206 @@
-use std::path::PathBuf;
+use std::{collections::BTreeMap, fmt};
 
-use config::Config;
-use serde::Deserialize;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use miette::{miette, LabeledSpan, Report};
+use regex::Regex;
+use serde::{Deserialize, Deserializer};
+use unicode_segmentation::UnicodeSegmentation;
 
-use crate::parser::Commit;
+use crate::parser::{Commit, CommitSpan};
 
 pub mod body_empty;
+pub mod body_leading_blank;
+pub mod body_pattern;
+pub mod co_authored_by;
+pub mod footer_leading_blank;
+pub mod footer_pattern;
+pub mod header_emoji;
+pub mod header_pattern;
+pub mod header_trim;
+pub mod references_empty;
+pub mod scope_case;
 pub mod scope_empty;
 pub mod scope_enum;
+pub mod scope_matches_paths;
 pub mod scope_max_length;
+pub mod signed_off_by;
+pub mod subject_full_stop;
+pub mod subject_pattern;
+pub mod trailer_exists;
+pub mod type_case;
+pub mod type_enum;
+
+/// Documentation of a rule, used for `commitguard rules` and `commitguard explain`
+pub struct RuleMeta {
+    /// Name of the rule as used in the config (e.g. `scope-enum`)
+    pub name: &'static str,
+    /// What the rule checks
+    pub description: &'static str,
+    /// Shape of the options in the config
+    pub options: &'static str,
+    /// Default options, which are used if the rule is not configured
+    pub default: &'static str,
+    /// Commit messages which are valid with the default options, rules which are `off` by default are turned on
+    pub good: &'static [&'static str],
+    /// Commit messages which are invalid with the default options, rules which are `off` by default are turned on
+    pub bad: &'static [&'static str],
+}
+
+/// Documentation of all available rules
+pub const RULES: [RuleMeta; 21] = [
+    body_empty::META,
+    body_leading_blank::META,
+    body_pattern::META,
+    co_authored_by::META,
+    footer_leading_blank::META,
+    footer_pattern::META,
+    header_emoji::META,
+    header_pattern::META,
+    header_trim::META,
+    references_empty::META,
+    scope_case::META,
+    scope_empty::META,
+    scope_enum::META,
+    scope_matches_paths::META,
+    scope_max_length::META,
+    signed_off_by::META,
+    subject_full_stop::META,
+    subject_pattern::META,
+    trailer_exists::META,
+    type_case::META,
+    type_enum::META,
+];
+
+/// Find the documentation of a rule by its name
+pub fn find_rule_meta(name: &str) -> Option<&'static RuleMeta> {
+    RULES.iter().find(|meta| meta.name == name)
+}
+
+/// Length of a text in grapheme clusters, i.e. the characters a reader sees (`é` or `👍🏽` count as one)
+pub fn text_length(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
+/// Link to the documentation of a rule
+pub fn rule_url(name: &str) -> String {
+    format!("https://github.com/zauni/commitguard#{}", name)
+}
 
 pub trait Rule {
+    /// Name of the rule as used in the config (e.g. `scope-enum`)
+    fn name(&self) -> &'static str;
+
+    /// Configured severity of the rule
+    fn severity(&self) -> &Severity;
+
     fn run(&self, commit: &Commit) -> Option<miette::Report>;
+
+    /// Like `run`, but with information about the commit which is not part of its message
+    /// (e.g. the author). Only rules which need this information have to implement it.
+    fn run_with_context(&self, commit: &Commit, _context: &LintContext) -> Option<miette::Report> {
+        self.run(commit)
+    }
+
+    /// Text edits which fix the violation found by `run`, if the rule can be fixed automatically
+    fn fix(&self, _commit: &Commit) -> Option<Vec<Fix>> {
+        None
+    }
+}
+
+/// Rules which replace the rules of the same name for commits of a type (`[rules.when.type.<type>]`)
+pub type TypeOverrides = BTreeMap<String, Vec<Box<dyn Rule>>>;
+
+/// Rules which apply to the commit: the overrides for its type replace the rules of the same name
+pub fn rules_for_commit<'r>(
+    commit: &Commit,
+    rules: &'r [Box<dyn Rule>],
+    overrides: &'r TypeOverrides,
+) -> Vec<&'r dyn Rule> {
+    let overrides = overrides
+        .get(&commit.commit_type.to_string())
+        .map_or(&[][..], Vec::as_slice);
+    let find_override = |name: &str| overrides.iter().find(|rule| rule.name() == name);
+
+    let mut applied: Vec<&dyn Rule> = rules
+        .iter()
+        .map(|rule| find_override(rule.name()).unwrap_or(rule).as_ref())
+        .collect();
+    // overrides of rules which are not configured at all
+    applied.extend(
+        overrides
+            .iter()
+            .filter(|rule| !rules.iter().any(|r| r.name() == rule.name()))
+            .map(|rule| rule.as_ref()),
+    );
+
+    applied
+}
+
+/// Prefixes of the rules which only check the header
+const HEADER_RULE_PREFIXES: [&str; 4] = ["header-", "type-", "scope-", "subject-"];
+
+/// Rules with the prefix of a header rule which also check more than the header
+const NOT_HEADER_RULES: [&str; 1] = [scope_matches_paths::META.name];
+
+/// Check if the rule only checks the header (e.g. `type-enum` or `subject-full-stop`)
+pub fn is_header_rule(name: &str) -> bool {
+    HEADER_RULE_PREFIXES
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+        && !NOT_HEADER_RULES.contains(&name)
+}
+
+/// Information about a commit which is not part of its message
+#[derive(Debug, Default, Clone)]
+pub struct LintContext {
+    /// Identity of the author (e.g. `Jane Doe <jane@example.com>`)
+    pub author: Option<String>,
+    /// Paths of the files which the commit changes, relative to the root of the repository
+    pub changed_files: Option<Vec<String>>,
+}
+
+/// A text edit which replaces a range of the commit message
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fix {
+    /// Start of the replaced range (byte offset into the raw commit message)
+    pub start: usize,
+    /// End of the replaced range (byte offset into the raw commit message)
+    pub end: usize,
+    /// Text which is put in place of the range
+    pub replacement: String,
+}
+
+impl Fix {
+    /// Replace the text of the span
+    pub fn replace(span: &CommitSpan, replacement: impl Into<String>) -> Self {
+        Fix {
+            start: span.start(),
+            end: span.end(),
+            replacement: replacement.into(),
+        }
+    }
+
+    /// Insert text at the given position
+    pub fn insert(position: usize, text: impl Into<String>) -> Self {
+        Fix {
+            start: position,
+            end: position,
+            replacement: text.into(),
+        }
+    }
+
+    /// Remove the text between `start` and `end`
+    pub fn delete(start: usize, end: usize) -> Self {
+        Fix {
+            start,
+            end,
+            replacement: String::new(),
+        }
+    }
 }
 
 /// Severity of the rule
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, PartialEq)]
 pub enum Severity {
     /// Turn off the rule
     #[serde(rename = "off")]
@@ -28,8 +213,19 @@ pub enum Severity {
     Error,
 }
 
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Severity::Off => "off",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{}", name)
+    }
+}
+
 /// When the rule should be applied
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub enum Condition {
     /// The options should "never" be found (e.g. in a list of disallowed values)
     #[serde(rename = "never")]
@@ -40,8 +236,8 @@ pub enum Condition {
 }
 
 /// Possible target cases for the rule (e.g. subject must start with a capital letter: `TargetCase::Sentence`)
-#[derive(Debug, Deserialize)]
-enum TargetCase {
+#[derive(Debug, Clone, Deserialize)]
+pub enum TargetCase {
     /// Lower case (e.g. `sometext`)
     #[serde(rename = "lower-case")]
     Lower,
@@ -68,49 +264,294 @@ enum TargetCase {
     Sentence,
 }
 
+impl TargetCase {
+    /// Convert the text to this case
+    pub fn convert(&self, text: &str) -> String {
+        let words = split_words(text);
+        let capitalize = |word: &String| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        };
+
+        match self {
+            TargetCase::Lower => text.to_lowercase(),
+            TargetCase::Upper => text.to_uppercase(),
+            TargetCase::Pascal => words.iter().map(capitalize).collect(),
+            TargetCase::Camel => words
+                .iter()
+                .enumerate()
+                .map(|(i, word)| {
+                    if i == 0 {
+                        word.clone()
+                    } else {
+                        capitalize(word)
+                    }
+                })
+                .collect(),
+            TargetCase::Kebab => words.join("-"),
+            TargetCase::Snake => words.join("_"),
+            TargetCase::Start => words.iter().map(capitalize).collect::<Vec<_>>().join(" "),
+            TargetCase::Sentence => {
+                // only the first word is changed, the rest of the text is kept as it is
+                let first_word_len = text.find(' ').unwrap_or(text.len());
+                capitalize(&text[..first_word_len].to_lowercase()) + &text[first_word_len..]
+            }
+        }
+    }
+
+    /// Check if the text is already in this case
+    pub fn matches(&self, text: &str) -> bool {
+        self.convert(text) == text
+    }
+}
+
+impl fmt::Display for TargetCase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            TargetCase::Lower => "lower-case",
+            TargetCase::Upper => "upper-case",
+            TargetCase::Pascal => "pascal-case",
+            TargetCase::Camel => "camel-case",
+            TargetCase::Kebab => "kebab-case",
+            TargetCase::Snake => "snake-case",
+            TargetCase::Start => "start-case",
+            TargetCase::Sentence => "sentence-case",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Split a text into lower case words at whitespace, `-`, `_` and lower to upper case changes
+fn split_words(text: &str) -> Vec<String> {
+    let mut words: Vec<String> = vec![];
+    let mut current = String::new();
+    let mut previous_is_lower = false;
+
+    for c in text.chars() {
+        if c.is_whitespace() || c == '-' || c == '_' {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            previous_is_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && previous_is_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        previous_is_lower = c.is_lowercase() || c.is_numeric();
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+
+    words
+}
+
 /// Options for all rules without options
 #[derive(Debug, Deserialize)]
-pub struct NoOpts(Severity, Condition);
+pub struct NoOpts(pub Severity, pub Condition);
 
 /// Options for all enum rules
+#[derive(Debug, Clone, Deserialize)]
+pub struct EnumOpts(pub Severity, pub Condition, pub Vec<String>);
+
+/// Values of an enum rule, either for every commit or by commit type
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum TypedValues {
+    /// The same values for every commit (e.g. `["api", "cli"]`)
+    All(Vec<String>),
+    /// Values by commit type (e.g. `{ ci = ["build", "release"], feat = ["api", "cli"] }`),
+    /// commits of other types are not checked
+    ByType(BTreeMap<String, Vec<String>>),
+}
+
+impl TypedValues {
+    /// Values for commits of the given type
+    pub fn for_type(&self, commit_type: &str) -> &[String] {
+        match self {
+            TypedValues::All(values) => values,
+            TypedValues::ByType(values) => values.get(commit_type).map_or(&[], Vec::as_slice),
+        }
+    }
+}
+
+impl Default for TypedValues {
+    fn default() -> Self {
+        TypedValues::All(vec![])
+    }
+}
+
+/// Options for all enum rules whose values can depend on the commit type
+#[derive(Debug, Deserialize)]
+pub struct TypedEnumOpts(pub Severity, pub Condition, pub TypedValues);
+
+/// Options for all rules which map values to the types they are allowed for (e.g. `{ "✨" = ["feat"] }`)
+#[derive(Debug, Deserialize)]
+pub struct TypeMapOpts(
+    pub Severity,
+    pub Condition,
+    #[serde(default)] pub BTreeMap<String, Vec<String>>,
+);
+
+/// Glob patterns of paths (e.g. `packages/api/**`), which are compiled when the config is loaded
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "Vec<String>")]
+pub struct PathGlobs(GlobSet);
+
+impl PathGlobs {
+    pub fn is_match(&self, path: &str) -> bool {
+        self.0.is_match(path)
+    }
+}
+
+impl TryFrom<Vec<String>> for PathGlobs {
+    type Error = globset::Error;
+
+    fn try_from(patterns: Vec<String>) -> Result<Self, Self::Error> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &patterns {
+            builder.add(Glob::new(pattern)?);
+        }
+
+        Ok(PathGlobs(builder.build()?))
+    }
+}
+
+/// Options for all rules which map values to paths (e.g. `{ api = ["packages/api/**"] }`)
 #[derive(Debug, Deserialize)]
-pub struct EnumOpts(Severity, Condition, Vec<String>);
+pub struct PathMapOpts(
+    pub Severity,
+    #[serde(default)] pub BTreeMap<String, PathGlobs>,
+);
 
 /// Options for all length rules
 #[derive(Debug, Deserialize)]
-pub struct LengthOpts(Severity, usize);
+pub struct LengthOpts(pub Severity, pub usize);
 
 /// Options for all case rules
+#[derive(Debug, Clone, Deserialize)]
+pub struct CaseOpts(pub Severity, pub Condition, pub TargetCase);
+
+/// Options for all rules which check for a specific value (e.g. the full stop character)
 #[derive(Debug, Deserialize)]
-pub struct CaseOpts(Severity, Condition, TargetCase);
+pub struct ValueOpts(pub Severity, pub Condition, pub String);
 
-/// Config all the rules
+/// Options for all rules with an optional list of allowed values (an empty list allows everything)
 #[derive(Debug, Deserialize)]
-struct RulesDetails {
-    #[serde(rename = "body-empty")]
-    body_empty: NoOpts,
-    #[serde(rename = "scope-empty")]
-    scope_empty: NoOpts,
-    #[serde(rename = "scope-enum")]
-    scope_enum: EnumOpts,
-    #[serde(rename = "scope-max-length")]
-    scope_max_length: LengthOpts,
-    // #[serde(rename = "scope-case")]
-    // scope_case: CaseOpts,
-}
-
-/// Config
+pub struct AllowlistOpts(pub Severity, #[serde(default)] pub Vec<String>);
+
+/// Options for all pattern rules
 #[derive(Debug, Deserialize)]
-struct RulesConfig {
-    rules: RulesDetails,
+pub struct PatternOpts(
+    pub Severity,
+    pub Condition,
+    #[serde(deserialize_with = "deserialize_regex")] pub Regex,
+);
+
+/// Compile the regex of the pattern rules while the config is loaded
+fn deserialize_regex<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Regex, D::Error> {
+    let pattern = String::deserialize(deserializer)?;
+
+    Regex::new(&pattern).map_err(serde::de::Error::custom)
 }
 
+/// Check a part of the commit with the options of a pattern rule.
+///
+/// With `never` every match is labeled, with `always` the whole part is labeled if there is no match.
+/// A missing part (e.g. a commit without body) can't match, so it is only reported with `always`.
+fn check_pattern(
+    name: &'static str,
+    part: &str,
+    span: Option<&CommitSpan>,
+    opts: &PatternOpts,
+    commit: &Commit,
+) -> Option<Report> {
+    let PatternOpts(severity, condition, regex) = opts;
+
+    if severity == &Severity::Off {
+        return None;
+    }
+
+    let (labels, problem): (Vec<LabeledSpan>, &str) = match (span, condition) {
+        (None, Condition::Never) => return None,
+        (None, Condition::Always) => (vec![], " is missing"),
+        (Some(span), Condition::Never) => {
+            let labels: Vec<LabeledSpan> = regex
+                .find_iter(&span.to_string())
+                .map(|found| {
+                    LabeledSpan::at(
+                        span.start() + found.start()..span.start() + found.end(),
+                        "not allowed",
+                    )
+                })
+                .collect();
+            if labels.is_empty() {
+                return None;
+            }
+            (labels, " matches a forbidden pattern")
+        }
+        (Some(span), Condition::Always) if regex.is_match(&span.to_string()) => return None,
+        (Some(span), Condition::Always) => (
+            vec![LabeledSpan::at(span.start()..span.end(), "doesn't match")],
+            " doesn't match the pattern",
+        ),
+    };
+
+    let mut title = part.to_string();
+    title[..1].make_ascii_uppercase();
+
+    Some(
+        miette!(
+            severity = match severity {
+                Severity::Warning => miette::Severity::Warning,
+                Severity::Error => miette::Severity::Error,
+                Severity::Off => miette::Severity::Advice,
+            },
+            labels = labels,
+            help = String::from(part)
+                + " must"
+                + match condition {
+                    Condition::Never => " not",
+                    Condition::Always => "",
+                }
+                + " match `"
+                + regex.as_str()
+                + "`",
+            code = format!("rule/{}", name),
+            url = rule_url(name),
+            "{}",
+            title + problem,
+        )
+        .with_source_code(commit.raw.clone()),
+    )
+}
+
+#[derive(Default)]
 pub struct LintResult {
     errors: Option<Vec<miette::Report>>,
     warnings: Option<Vec<miette::Report>>,
+    /// Names of all rules which were not turned off
+    evaluated: Vec<&'static str>,
+    /// Names of all evaluated rules without a violation
+    passed: Vec<&'static str>,
 }
 
 impl LintResult {
+    /// Result for a commit message which could not be parsed, so no rule was evaluated
+    pub fn from_parse_error(report: miette::Report) -> Self {
+        LintResult {
+            errors: Some(vec![report]),
+            warnings: None,
+            evaluated: vec![],
+            passed: vec![],
+        }
+    }
+
     pub fn errors(&self) -> Option<&Vec<miette::Report>> {
         self.errors.as_ref()
     }
@@ -140,55 +581,48 @@ impl LintResult {
     pub fn has_warnings(&self) -> bool {
         self.warnings.is_some() && !self.warnings().unwrap().is_empty()
     }
+
+    pub fn evaluated(&self) -> &[&'static str] {
+        &self.evaluated
+    }
+
+    pub fn passed(&self) -> &[&'static str] {
+        &self.passed
+    }
+}
+
+/// Run all given rules (with the overrides for the type of the commit) against the commit
+/// and return all found errors and warnings
+pub fn lint(
+    commit: &Commit,
+    rules: &[Box<dyn Rule>],
+    overrides: &TypeOverrides,
+    context: &LintContext,
+) -> LintResult {
+    lint_rules(commit, rules_for_commit(commit, rules, overrides), context)
 }
 
-pub fn run(commit: &Commit, config_path: PathBuf) -> LintResult {
-    let settings = Config::builder()
-        .add_source(config::File::from_str(
-            r#"
-          [rules]
-
-          body-empty = ["off", "never"]
-          scope-empty = ["error", "never"]
-          scope-enum = ["error", "always", ["foo", "bar", "baz"]]
-          scope-max-length = ["error", 20]
-          scope-case = ["error", "always", "lower-case"]
-        "#,
-            config::FileFormat::Toml,
-        ))
-        // Source can be `commitlint.config.toml` or `commitlint.config.json``
-        .add_source(config::File::from(config_path).required(false))
-        .build()
-        .unwrap();
-
-    // Print out our settings
-    let config: RulesConfig = settings.try_deserialize::<RulesConfig>().unwrap();
-    // println!("{:?}", config);
-
-    // create list of rules to iterate over them
-    let rules: Vec<Box<dyn Rule>> = vec![
-        Box::new(body_empty::BodyEmptyRule {
-            opts: config.rules.body_empty,
-        }),
-        Box::new(scope_empty::ScopeEmptyRule {
-            opts: config.rules.scope_empty,
-        }),
-        Box::new(scope_enum::ScopeEnumRule {
-            opts: config.rules.scope_enum,
-        }),
-        Box::new(scope_max_length::ScopeMaxLengthRule {
-            opts: config.rules.scope_max_length,
-        }),
-    ];
-
-    // iterate over all rules and run them and return all found errors and warnings
+/// Run the given rules against the commit, the type overrides are already applied
+pub fn lint_rules<'r>(
+    commit: &Commit,
+    rules: impl IntoIterator<Item = &'r dyn Rule>,
+    context: &LintContext,
+) -> LintResult {
     let mut lint_result = LintResult {
         errors: None,
         warnings: None,
+        evaluated: vec![],
+        passed: vec![],
     };
     for rule in rules {
-        if let Some(report) = rule.run(commit) {
-            match report.severity() {
+        if rule.severity() == &Severity::Off {
+            continue;
+        }
+        lint_result.evaluated.push(rule.name());
+
+        match rule.run_with_context(commit, context) {
+            None => lint_result.passed.push(rule.name()),
+            Some(report) => match report.severity() {
                 Some(miette::Severity::Error) => {
                     if lint_result.errors.is_none() {
                         lint_result.errors = Some(vec![]);
@@ -202,7 +636,7 @@ pub fn run(commit: &Commit, config_path: PathBuf) -> LintResult {
                     lint_result.warnings.as_mut().unwrap().push(report);
                 }
                 _ => {}
-            }
+            },
         }
     }
 
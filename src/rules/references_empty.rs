@@ -0,0 +1,120 @@
+use crate::parser::Commit;
+
+use super::{rule_url, Condition, NoOpts, Rule, RuleMeta, Severity};
+use miette::{miette, LabeledSpan, Report};
+
+pub const META: RuleMeta = RuleMeta {
+    name: "references-empty",
+    description: "References to issues must not be empty (`never`) or must be empty (`always`)",
+    options: "[severity, condition]",
+    default: r#"["off", "never"]"#,
+    good: &["fix: crash on start\n\nsome body\n\nCloses #123"],
+    bad: &["fix: crash on start"],
+};
+
+pub struct ReferencesEmptyRule {
+    pub opts: NoOpts,
+}
+
+impl Rule for ReferencesEmptyRule {
+    fn name(&self) -> &'static str {
+        META.name
+    }
+
+    fn severity(&self) -> &Severity {
+        &self.opts.0
+    }
+
+    fn run(&self, commit: &Commit) -> Option<Report> {
+        let severity = &self.opts.0;
+        let condition = &self.opts.1;
+
+        if severity == &Severity::Off {
+            return None;
+        }
+
+        let is_valid = match condition {
+            Condition::Never => !commit.references.is_empty(),
+            Condition::Always => commit.references.is_empty(),
+        };
+
+        if !is_valid {
+            return Some(
+                miette!(
+                    severity = match severity {
+                        Severity::Warning => miette::Severity::Warning,
+                        Severity::Error => miette::Severity::Error,
+                        Severity::Off => miette::Severity::Advice,
+                    },
+                    labels = commit
+                        .references
+                        .iter()
+                        .map(|reference| LabeledSpan::at(
+                            reference.span.start()..reference.span.end(),
+                            "not allowed reference"
+                        ))
+                        .collect::<Vec<_>>(),
+                    help = String::from("references")
+                        + match condition {
+                            Condition::Never => " may not be empty, e.g. add `Closes #123`",
+                            Condition::Always => " must be empty",
+                        },
+                    code = "rule/references-empty",
+                    url = rule_url(META.name),
+                    "{}",
+                    match condition {
+                        Condition::Never => "References are empty",
+                        Condition::Always => "References are not empty",
+                    },
+                )
+                .with_source_code(commit.raw.clone()),
+            );
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_commit;
+
+    use super::*;
+
+    #[test]
+    fn test_never_condition() {
+        let rule = ReferencesEmptyRule {
+            opts: NoOpts(Severity::Error, Condition::Never),
+        };
+
+        let commit = parse_commit("fix: crash on start\n\nsome body\n\nCloses #123").unwrap();
+        assert!(rule.run(&commit).is_none());
+
+        let commit = parse_commit("fix: crash on start").unwrap();
+        assert!(rule.run(&commit).is_some());
+    }
+
+    #[test]
+    fn test_always_condition() {
+        let rule = ReferencesEmptyRule {
+            opts: NoOpts(Severity::Error, Condition::Always),
+        };
+
+        let commit = parse_commit("fix: crash on start").unwrap();
+        assert!(rule.run(&commit).is_none());
+
+        let commit = parse_commit("fix: crash on start (#12)\n\nsome body\n\nCloses #123").unwrap();
+        let report = rule.run(&commit).unwrap();
+        assert_eq!(report.labels().unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_severity_off() {
+        let rule = ReferencesEmptyRule {
+            opts: NoOpts(Severity::Off, Condition::Never),
+        };
+
+        let commit = parse_commit("fix: crash on start").unwrap();
+        assert!(rule.run(&commit).is_none());
+    }
+}
@@ -0,0 +1,169 @@
+use crate::parser::Commit;
+
+use super::{rule_url, CaseOpts, Condition, Fix, Rule, RuleMeta, Severity};
+use miette::{miette, LabeledSpan, Report};
+
+pub const META: RuleMeta = RuleMeta {
+    name: "scope-case",
+    description: "Scope must (`always`) or must not (`never`) be in the given case",
+    options: "[severity, condition, \"lower-case\" | \"upper-case\" | \"pascal-case\" | \"camel-case\" | \"kebab-case\" | \"snake-case\" | \"start-case\" | \"sentence-case\"]",
+    default: r#"["off", "always", "lower-case"]"#,
+    good: &["feat(api): add feature"],
+    bad: &["feat(API): add feature"],
+};
+
+pub struct ScopeCaseRule {
+    pub opts: CaseOpts,
+}
+
+impl Rule for ScopeCaseRule {
+    fn name(&self) -> &'static str {
+        META.name
+    }
+
+    fn severity(&self) -> &Severity {
+        &self.opts.0
+    }
+
+    fn run(&self, commit: &Commit) -> Option<Report> {
+        let severity = &self.opts.0;
+        let condition = &self.opts.1;
+        let case = &self.opts.2;
+
+        if severity == &Severity::Off {
+            return None;
+        }
+
+        let labels: Vec<LabeledSpan> = commit
+            .scopes
+            .iter()
+            .filter(|scope| {
+                let is_in_case = case.matches(&scope.to_string());
+                match condition {
+                    Condition::Never => is_in_case,
+                    Condition::Always => !is_in_case,
+                }
+            })
+            .map(|scope| LabeledSpan::at(scope.start()..scope.end(), "wrong case"))
+            .collect();
+
+        if !labels.is_empty() {
+            return Some(
+                miette!(
+                    severity = match severity {
+                        Severity::Warning => miette::Severity::Warning,
+                        Severity::Error => miette::Severity::Error,
+                        Severity::Off => miette::Severity::Advice,
+                    },
+                    labels = labels,
+                    help = String::from("scope must")
+                        + match condition {
+                            Condition::Never => " not",
+                            Condition::Always => "",
+                        }
+                        + " be in "
+                        + case.to_string().as_str(),
+                    code = "rule/scope-case",
+                    url = rule_url(META.name),
+                    "Scope has the wrong case",
+                )
+                .with_source_code(commit.raw.clone()),
+            );
+        }
+
+        None
+    }
+
+    fn fix(&self, commit: &Commit) -> Option<Vec<Fix>> {
+        if commit.scopes.is_empty() {
+            return None;
+        }
+
+        match self.opts.1 {
+            Condition::Never => None,
+            Condition::Always => Some(
+                commit
+                    .scopes
+                    .iter()
+                    .map(|scope| Fix::replace(scope, self.opts.2.convert(&scope.to_string())))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::CommitSpan, rules::TargetCase};
+
+    use super::*;
+
+    #[test]
+    fn test_empty_scope() {
+        let commit = Commit {
+            scope: None,
+            ..Default::default()
+        };
+
+        let rule = ScopeCaseRule {
+            opts: CaseOpts(Severity::Error, Condition::Always, TargetCase::Kebab),
+        };
+
+        assert!(rule.run(&commit).is_none());
+        assert!(rule.fix(&commit).is_none());
+    }
+
+    #[test]
+    fn test_always_condition() {
+        let mut commit: Commit<'_> = Default::default();
+
+        let rule = ScopeCaseRule {
+            opts: CaseOpts(Severity::Error, Condition::Always, TargetCase::Kebab),
+        };
+
+        commit.scopes = vec![CommitSpan::new("my-scope", 5, 13)];
+        assert!(rule.run(&commit).is_none());
+
+        commit.scopes = vec![CommitSpan::new("myScope", 5, 12)];
+        assert!(rule.run(&commit).is_some());
+        assert_eq!(
+            rule.fix(&commit),
+            Some(vec![Fix::replace(&commit.scopes[0], "my-scope")])
+        );
+    }
+
+    #[test]
+    fn test_never_condition() {
+        let mut commit: Commit<'_> = Default::default();
+
+        let rule = ScopeCaseRule {
+            opts: CaseOpts(Severity::Error, Condition::Never, TargetCase::Upper),
+        };
+
+        commit.scopes = vec![CommitSpan::new("API", 5, 8)];
+        assert!(rule.run(&commit).is_some());
+        assert!(rule.fix(&commit).is_none());
+
+        commit.scopes = vec![CommitSpan::new("api", 5, 8)];
+        assert!(rule.run(&commit).is_none());
+    }
+
+    #[test]
+    fn test_multiple_scopes() {
+        let rule = ScopeCaseRule {
+            opts: CaseOpts(Severity::Error, Condition::Always, TargetCase::Lower),
+        };
+
+        let commit = crate::parser::parse_commit("feat(api,UI,Db): add feature").unwrap();
+        let report = rule.run(&commit).unwrap();
+        assert_eq!(report.labels().unwrap().count(), 2);
+        assert_eq!(
+            rule.fix(&commit),
+            Some(vec![
+                Fix::replace(&commit.scopes[0], "api"),
+                Fix::replace(&commit.scopes[1], "ui"),
+                Fix::replace(&commit.scopes[2], "db"),
+            ])
+        );
+    }
+}
@@ -1,13 +1,30 @@
 use crate::parser::Commit;
 
-use super::{Condition, NoOpts, Rule, Severity};
+use super::{rule_url, Condition, NoOpts, Rule, RuleMeta, Severity};
 use miette::{miette, LabeledSpan, Report};
 
+pub const META: RuleMeta = RuleMeta {
+    name: "scope-empty",
+    description: "Scope must not be empty (`never`) or must be empty (`always`)",
+    options: "[severity, condition]",
+    default: r#"["error", "never"]"#,
+    good: &["feat(api): add feature"],
+    bad: &["feat: add feature"],
+};
+
 pub struct ScopeEmptyRule {
     pub opts: NoOpts,
 }
 
 impl Rule for ScopeEmptyRule {
+    fn name(&self) -> &'static str {
+        META.name
+    }
+
+    fn severity(&self) -> &Severity {
+        &self.opts.0
+    }
+
     fn run(&self, commit: &Commit) -> Option<Report> {
         let severity = &self.opts.0;
         let condition = &self.opts.1;
@@ -45,8 +62,12 @@ impl Rule for ScopeEmptyRule {
                             Condition::Always => " must be empty",
                         },
                     code = "rule/scope-empty",
-                    url = "https://example.com",
-                    "Scope",
+                    url = rule_url(META.name),
+                    "{}",
+                    match condition {
+                        Condition::Never => "Scope is empty",
+                        Condition::Always => "Scope is not empty",
+                    },
                 )
                 .with_source_code(commit.raw.clone()),
             );
@@ -57,6 +78,7 @@ impl Rule for ScopeEmptyRule {
 }
 
 #[cfg(test)]
+#[allow(clippy::field_reassign_with_default)]
 mod tests {
     use super::*;
 
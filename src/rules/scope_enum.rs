@@ -1,54 +1,80 @@
 use crate::parser::Commit;
 
-use super::{Condition, EnumOpts, Rule, Severity};
+use super::{rule_url, Condition, Rule, RuleMeta, Severity, TypedEnumOpts, TypedValues};
 use miette::{miette, LabeledSpan, Report};
 
+pub const META: RuleMeta = RuleMeta {
+    name: "scope-enum",
+    description: "Scope must be one of (`always`) or must not be one of (`never`) the given scopes. The scopes can also be given by commit type, commits of other types are not checked.",
+    options: "[severity, condition, [scopes] | { type = [scopes] }]",
+    default: r#"["error", "always", ["foo", "bar", "baz"]]"#,
+    good: &["feat(foo): add feature"],
+    bad: &["feat(nice): add feature"],
+};
+
 pub struct ScopeEnumRule {
-    pub opts: EnumOpts,
+    pub opts: TypedEnumOpts,
 }
 
 impl Rule for ScopeEnumRule {
+    fn name(&self) -> &'static str {
+        META.name
+    }
+
+    fn severity(&self) -> &Severity {
+        &self.opts.0
+    }
+
     fn run(&self, commit: &Commit) -> Option<Report> {
         let severity = &self.opts.0;
         let condition = &self.opts.1;
-        let scopes = &self.opts.2;
+        let scopes = self.opts.2.for_type(&commit.commit_type.to_string());
 
         if severity == &Severity::Off || scopes.is_empty() {
             return None;
         }
 
-        if let Some(scope) = &commit.scope {
-            let is_in_scopes = scopes.contains(&scope.to_string());
-            let is_valid = match condition {
-                Condition::Never => !is_in_scopes,
-                Condition::Always => is_in_scopes,
-            };
-            if !is_valid {
-                return Some(
-                    miette!(
-                        severity = match severity {
-                            Severity::Warning => miette::Severity::Warning,
-                            Severity::Error => miette::Severity::Error,
-                            Severity::Off => miette::Severity::Advice,
-                        },
-                        labels = vec![LabeledSpan::at(
-                            scope.start()..scope.end(),
-                            "not allowed scope"
-                        ),],
-                        help = String::from("scope must")
-                            + match condition {
-                                Condition::Never => " not",
-                                Condition::Always => "",
-                            }
-                            + " be one of "
-                            + &scopes.join(", "),
-                        code = "rule/scope-enum",
-                        url = "https://example.com",
-                        "Scope not allowed",
-                    )
-                    .with_source_code(commit.raw.clone()),
-                );
-            }
+        let labels: Vec<LabeledSpan> = commit
+            .scopes
+            .iter()
+            .filter(|scope| {
+                let is_in_scopes = scopes.contains(&scope.to_string());
+                match condition {
+                    Condition::Never => is_in_scopes,
+                    Condition::Always => !is_in_scopes,
+                }
+            })
+            .map(|scope| LabeledSpan::at(scope.start()..scope.end(), "not allowed scope"))
+            .collect();
+
+        if !labels.is_empty() {
+            return Some(
+                miette!(
+                    severity = match severity {
+                        Severity::Warning => miette::Severity::Warning,
+                        Severity::Error => miette::Severity::Error,
+                        Severity::Off => miette::Severity::Advice,
+                    },
+                    labels = labels,
+                    help = String::from("scope must")
+                        + match condition {
+                            Condition::Never => " not",
+                            Condition::Always => "",
+                        }
+                        + " be one of "
+                        + scopes.join(", ").as_str()
+                        + match &self.opts.2 {
+                            TypedValues::All(_) => String::new(),
+                            TypedValues::ByType(_) =>
+                                format!(" for `{}` commits", commit.commit_type),
+                        }
+                        .as_str(),
+                    code = "rule/scope-enum",
+                    url = rule_url(META.name),
+                    "Scope not allowed",
+                )
+                .with_source_code(commit.raw.clone()),
+            );
         }
 
         None
@@ -56,8 +82,9 @@ impl Rule for ScopeEnumRule {
 }
 
 #[cfg(test)]
+#[allow(clippy::field_reassign_with_default)]
 mod tests {
-    use crate::parser::CommitSpan;
+    use crate::parser::{parse_commit, CommitSpan};
 
     use super::*;
 
@@ -68,20 +95,20 @@ mod tests {
 
         // If the scope is empty this rule behaves the same as it would be Severity::Off
         let rule = ScopeEnumRule {
-            opts: EnumOpts(
+            opts: TypedEnumOpts(
                 Severity::Error,
                 Condition::Never,
-                vec!["feat".to_string(), "fix".to_string()],
+                TypedValues::All(vec!["feat".to_string(), "fix".to_string()]),
             ),
         };
 
         assert!(rule.run(&commit).is_none());
 
         let rule = ScopeEnumRule {
-            opts: EnumOpts(
+            opts: TypedEnumOpts(
                 Severity::Error,
                 Condition::Always,
-                vec!["feat".to_string(), "fix".to_string()],
+                TypedValues::All(vec!["feat".to_string(), "fix".to_string()]),
             ),
         };
 
@@ -94,23 +121,23 @@ mod tests {
 
         // If the condition is `Never` the scope should not be in the list of disallowed scopes
         let rule = ScopeEnumRule {
-            opts: EnumOpts(
+            opts: TypedEnumOpts(
                 Severity::Error,
                 Condition::Never,
-                vec!["feat".to_string(), "fix".to_string()],
+                TypedValues::All(vec!["feat".to_string(), "fix".to_string()]),
             ),
         };
 
         // an empty scope is not disallowed, so return None
-        commit.scope = Some(Default::default());
+        commit.scopes = vec![Default::default()];
         assert!(rule.run(&commit).is_none());
 
         // the scope "nice" is not disallowed, so return None
-        commit.scope = Some(CommitSpan::new("nice", 0, 4));
+        commit.scopes = vec![CommitSpan::new("nice", 0, 4)];
         assert!(rule.run(&commit).is_none());
 
         // the scope "feat" is disallowed, so return a Report
-        commit.scope = Some(CommitSpan::new("feat", 0, 4));
+        commit.scopes = vec![CommitSpan::new("feat", 0, 4)];
         assert!(rule.run(&commit).is_some());
     }
 
@@ -120,23 +147,23 @@ mod tests {
 
         // If the condition is `Always` the scope must be in the list of allowed scopes
         let rule = ScopeEnumRule {
-            opts: EnumOpts(
+            opts: TypedEnumOpts(
                 Severity::Error,
                 Condition::Always,
-                vec!["feat".to_string(), "fix".to_string()],
+                TypedValues::All(vec!["feat".to_string(), "fix".to_string()]),
             ),
         };
 
         // an empty scope is not allowed, so return a Report
-        commit.scope = Some(Default::default());
+        commit.scopes = vec![Default::default()];
         assert!(rule.run(&commit).is_some());
 
         // the scope "nice" is not allowed, so return a Report
-        commit.scope = Some(CommitSpan::new("nice", 0, 4));
+        commit.scopes = vec![CommitSpan::new("nice", 0, 4)];
         assert!(rule.run(&commit).is_some());
 
         // the scope "feat" is allowed, so return None
-        commit.scope = Some(CommitSpan::new("feat", 0, 4));
+        commit.scopes = vec![CommitSpan::new("feat", 0, 4)];
         assert!(rule.run(&commit).is_none());
     }
 
@@ -146,18 +173,18 @@ mod tests {
 
         // If the severity is `Off`, the rule should return `None`
         let rule = ScopeEnumRule {
-            opts: EnumOpts(
+            opts: TypedEnumOpts(
                 Severity::Off,
                 Condition::Always,
-                vec!["feat".to_string(), "fix".to_string()],
+                TypedValues::All(vec!["feat".to_string(), "fix".to_string()]),
             ),
         };
 
-        commit.scope = Some(Default::default());
+        commit.scopes = vec![Default::default()];
         assert!(rule.run(&commit).is_none());
-        commit.scope = Some(CommitSpan::new("nice", 0, 4));
+        commit.scopes = vec![CommitSpan::new("nice", 0, 4)];
         assert!(rule.run(&commit).is_none());
-        commit.scope = Some(CommitSpan::new("feat", 0, 4));
+        commit.scopes = vec![CommitSpan::new("feat", 0, 4)];
         assert!(rule.run(&commit).is_none());
     }
 
@@ -166,14 +193,89 @@ mod tests {
         let mut commit: Commit<'_> = Default::default();
 
         let rule = ScopeEnumRule {
-            opts: EnumOpts(Severity::Error, Condition::Always, vec![]),
+            opts: TypedEnumOpts(Severity::Error, Condition::Always, TypedValues::All(vec![])),
         };
 
-        commit.scope = Some(Default::default());
+        commit.scopes = vec![Default::default()];
+        assert!(rule.run(&commit).is_none());
+        commit.scopes = vec![CommitSpan::new("nice", 0, 4)];
         assert!(rule.run(&commit).is_none());
-        commit.scope = Some(CommitSpan::new("nice", 0, 4));
+        commit.scopes = vec![CommitSpan::new("feat", 0, 4)];
         assert!(rule.run(&commit).is_none());
-        commit.scope = Some(CommitSpan::new("feat", 0, 4));
+    }
+
+    #[test]
+    fn test_multiple_scopes() {
+        let rule = ScopeEnumRule {
+            opts: TypedEnumOpts(
+                Severity::Error,
+                Condition::Always,
+                TypedValues::All(vec!["api".to_string(), "ui".to_string()]),
+            ),
+        };
+
+        let commit = parse_commit("feat(api,ui): add feature").unwrap();
         assert!(rule.run(&commit).is_none());
+
+        let commit = parse_commit("feat(cli,api,db): add feature").unwrap();
+        let report = rule.run(&commit).unwrap();
+        let labels: Vec<_> = report.labels().unwrap().collect();
+        assert_eq!(labels.len(), 2);
+        assert_eq!((labels[0].offset(), labels[0].len()), (5, 3));
+        assert_eq!((labels[1].offset(), labels[1].len()), (13, 2));
+    }
+
+    #[test]
+    fn test_scopes_by_type() {
+        let rule = ScopeEnumRule {
+            opts: serde_json::from_str(
+                r#"["error", "always", {"ci": ["github"], "feat": ["api", "cli"]}]"#,
+            )
+            .unwrap(),
+        };
+
+        let commit = parse_commit("feat(cli): add feature").unwrap();
+        assert!(rule.run(&commit).is_none());
+
+        let commit = parse_commit("ci(cli): add workflow").unwrap();
+        let report = rule.run(&commit).unwrap();
+        assert_eq!(
+            report.help().unwrap().to_string(),
+            "scope must be one of github for `ci` commits"
+        );
+
+        // commits of other types are not checked
+        let commit = parse_commit("docs(readme): add usage").unwrap();
+        assert!(rule.run(&commit).is_none());
+    }
+
+    #[test]
+    fn test_scopes_by_type_from_config() {
+        let config = crate::config::RulesConfig::from_toml(
+            r#"
+            [rules]
+            scope-enum = ["error", "always", { ci = ["github"], feat = ["api"] }]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.allowed_scopes().for_type("ci"), ["github"]);
+        assert!(config.allowed_scopes().for_type("docs").is_empty());
+    }
+
+    #[test]
+    fn test_package_scopes() {
+        let config = crate::config::RulesConfig::from_toml(
+            r#"
+            [rules]
+            scope-enum = ["error", "always", ["@org/pkg", "ui"]]
+            "#,
+        )
+        .unwrap();
+        let linter = crate::Linter::from_config(config).unwrap();
+
+        // `/` is part of the scope with the default parser options
+        assert!(!linter.lint("feat(@org/pkg,ui): add feature").has_errors());
+        assert!(linter.lint("feat(@org/other): add feature").has_errors());
     }
 }
@@ -0,0 +1,208 @@
+use crate::parser::Commit;
+
+use super::{rule_url, LintContext, PathMapOpts, Rule, RuleMeta, Severity};
+use miette::{miette, LabeledSpan, Report};
+
+pub const META: RuleMeta = RuleMeta {
+    name: "scope-matches-paths",
+    description: "Scopes must name the packages whose files the commit changes. The changed files are read from git (staged files, or the files of each commit with `--from`) or given with `--changed-files`. The examples map `{ api = [\"packages/api/**\"] }` and change `packages/api/src/lib.rs`.",
+    options: "[severity, { scope = [globs] }]",
+    default: r#"["off", {}]"#,
+    good: &["feat(api): add endpoint"],
+    bad: &["feat(ui): add endpoint"],
+};
+
+pub struct ScopeMatchesPathsRule {
+    pub opts: PathMapOpts,
+}
+
+impl Rule for ScopeMatchesPathsRule {
+    fn name(&self) -> &'static str {
+        META.name
+    }
+
+    fn severity(&self) -> &Severity {
+        &self.opts.0
+    }
+
+    fn run(&self, commit: &Commit) -> Option<Report> {
+        self.run_with_context(commit, &LintContext::default())
+    }
+
+    fn run_with_context(&self, commit: &Commit, context: &LintContext) -> Option<Report> {
+        let severity = &self.opts.0;
+        let packages = &self.opts.1;
+
+        if severity == &Severity::Off || packages.is_empty() {
+            return None;
+        }
+        // nothing can be checked without knowing the changed files
+        let files = context.changed_files.as_ref()?;
+
+        let changed: Vec<&str> = packages
+            .iter()
+            .filter(|(_, globs)| files.iter().any(|file| globs.is_match(file)))
+            .map(|(scope, _)| scope.as_str())
+            .collect();
+
+        let mut labels: Vec<LabeledSpan> = commit
+            .scopes
+            .iter()
+            .filter(|scope| {
+                packages.contains_key(scope.as_str()) && !changed.contains(&scope.as_str())
+            })
+            .map(|scope| LabeledSpan::at(scope.start()..scope.end(), "no changed files"))
+            .collect();
+
+        let missing: Vec<&str> = changed
+            .iter()
+            .copied()
+            .filter(|package| !commit.scopes.iter().any(|scope| scope.as_str() == *package))
+            .collect();
+        if !missing.is_empty() {
+            let label = format!("missing {}", missing.join(", "));
+            labels.push(match &commit.scope {
+                Some(scope) => LabeledSpan::at(scope.start()..scope.end(), label),
+                None => LabeledSpan::at_offset(commit.commit_type.end(), label),
+            });
+        }
+
+        if labels.is_empty() {
+            return None;
+        }
+
+        Some(
+            miette!(
+                severity = match severity {
+                    Severity::Warning => miette::Severity::Warning,
+                    Severity::Error => miette::Severity::Error,
+                    Severity::Off => miette::Severity::Advice,
+                },
+                labels = labels,
+                help = match changed.is_empty() {
+                    true => String::from("the changed files don't belong to any package"),
+                    false => format!(
+                        "scope must name the changed packages: {}",
+                        changed.join(", ")
+                    ),
+                },
+                code = "rule/scope-matches-paths",
+                url = rule_url(META.name),
+                "Scope doesn't match the changed files",
+            )
+            .with_source_code(commit.raw.clone()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_commit;
+
+    use super::*;
+
+    fn rule() -> ScopeMatchesPathsRule {
+        ScopeMatchesPathsRule {
+            opts: serde_json::from_str(
+                r#"["error", { "api": ["packages/api/**"], "ui": ["packages/ui/**", "assets/*.css"] }]"#,
+            )
+            .unwrap(),
+        }
+    }
+
+    fn context(files: &[&str]) -> LintContext {
+        LintContext {
+            changed_files: Some(files.iter().map(|file| file.to_string()).collect()),
+            ..Default::default()
+        }
+    }
+
+    fn label_texts(report: &Report) -> Vec<String> {
+        report
+            .labels()
+            .unwrap()
+            .map(|label| label.label().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_matching_scopes() {
+        let rule = rule();
+
+        let commit = parse_commit("feat(api): add endpoint").unwrap();
+        let files = context(&["packages/api/src/lib.rs", "README.md"]);
+        assert!(rule.run_with_context(&commit, &files).is_none());
+
+        let commit = parse_commit("feat(api,ui): add endpoint").unwrap();
+        let files = context(&["packages/api/src/lib.rs", "assets/main.css"]);
+        assert!(rule.run_with_context(&commit, &files).is_none());
+
+        // files outside of the packages don't need a scope
+        let commit = parse_commit("chore: update readme").unwrap();
+        assert!(rule
+            .run_with_context(&commit, &context(&["README.md"]))
+            .is_none());
+    }
+
+    #[test]
+    fn test_scope_without_changes() {
+        let commit = parse_commit("feat(ui): add endpoint").unwrap();
+        let report = rule()
+            .run_with_context(&commit, &context(&["packages/api/src/lib.rs"]))
+            .unwrap();
+
+        assert_eq!(label_texts(&report), ["no changed files", "missing api"]);
+        assert_eq!(
+            report.help().unwrap().to_string(),
+            "scope must name the changed packages: api"
+        );
+    }
+
+    #[test]
+    fn test_missing_scope() {
+        let commit = parse_commit("feat: add endpoint").unwrap();
+        let report = rule()
+            .run_with_context(
+                &commit,
+                &context(&["packages/api/src/lib.rs", "packages/ui/index.ts"]),
+            )
+            .unwrap();
+
+        assert_eq!(label_texts(&report), ["missing api, ui"]);
+        let labels: Vec<_> = report.labels().unwrap().collect();
+        assert_eq!(labels[0].offset(), 4);
+    }
+
+    #[test]
+    fn test_unknown_changed_files() {
+        let commit = parse_commit("feat(ui): add endpoint").unwrap();
+        assert!(rule().run(&commit).is_none());
+    }
+
+    #[test]
+    fn test_invalid_globs() {
+        assert!(
+            serde_json::from_str::<PathMapOpts>(r#"["error", { "api": ["packages/[api"] }]"#)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_no_header_rule() {
+        // the rule needs the changed files, so titles of pull requests are not checked by it
+        assert!(!crate::rules::is_header_rule(META.name));
+        assert!(crate::rules::is_header_rule("scope-enum"));
+    }
+
+    #[test]
+    fn test_severity_off() {
+        let rule = ScopeMatchesPathsRule {
+            opts: serde_json::from_str(r#"["off", { "api": ["packages/api/**"] }]"#).unwrap(),
+        };
+
+        let commit = parse_commit("feat(ui): add endpoint").unwrap();
+        assert!(rule
+            .run_with_context(&commit, &context(&["packages/api/src/lib.rs"]))
+            .is_none());
+    }
+}
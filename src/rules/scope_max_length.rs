@@ -1,13 +1,30 @@
 use crate::parser::Commit;
 
-use super::{LengthOpts, Rule, Severity};
+use super::{rule_url, text_length, LengthOpts, Rule, RuleMeta, Severity};
 use miette::{miette, LabeledSpan, Report};
 
+pub const META: RuleMeta = RuleMeta {
+    name: "scope-max-length",
+    description: "Scope must not be longer than the given number of characters",
+    options: "[severity, length]",
+    default: r#"["error", 20]"#,
+    good: &["feat(api): add feature"],
+    bad: &["feat(averyveryveryverylongscope): add feature"],
+};
+
 pub struct ScopeMaxLengthRule {
     pub opts: LengthOpts,
 }
 
 impl Rule for ScopeMaxLengthRule {
+    fn name(&self) -> &'static str {
+        META.name
+    }
+
+    fn severity(&self) -> &Severity {
+        &self.opts.0
+    }
+
     fn run(&self, commit: &Commit) -> Option<Report> {
         let severity = &self.opts.0;
         let max_length = &self.opts.1;
@@ -16,34 +33,86 @@ impl Rule for ScopeMaxLengthRule {
             return None;
         }
 
-        if let Some(scope) = &commit.scope {
-            let is_valid = scope.to_string().len() <= *max_length;
-            if !is_valid {
-                return Some(
-                    miette!(
-                        severity = match severity {
-                            Severity::Warning => miette::Severity::Warning,
-                            Severity::Error => miette::Severity::Error,
-                            Severity::Off => miette::Severity::Advice,
-                        },
-                        labels = vec![LabeledSpan::at(
+        let too_long: Vec<_> = commit
+            .scopes
+            .iter()
+            .filter(|scope| text_length(&scope.to_string()) > *max_length)
+            .collect();
+
+        if !too_long.is_empty() {
+            return Some(
+                miette!(
+                    severity = match severity {
+                        Severity::Warning => miette::Severity::Warning,
+                        Severity::Error => miette::Severity::Error,
+                        Severity::Off => miette::Severity::Advice,
+                    },
+                    labels = too_long
+                        .iter()
+                        .map(|scope| LabeledSpan::at(
                             scope.start()..scope.end(),
-                            "not allowed scope"
-                        ),],
-                        help = String::from("scope must not be longer than ")
-                            + &max_length.to_string()
-                            + " characters (current length: "
-                            + &scope.to_string().len().to_string()
-                            + ")",
-                        code = "rule/scope-max-length",
-                        url = "https://example.com",
-                        "Scope too long",
-                    )
-                    .with_source_code(commit.raw.clone()),
-                );
-            }
+                            format!("{} characters", text_length(&scope.to_string()))
+                        ))
+                        .collect::<Vec<_>>(),
+                    help = String::from("scope must not be longer than ")
+                        + max_length.to_string().as_str()
+                        + " characters",
+                    code = "rule/scope-max-length",
+                    url = rule_url(META.name),
+                    "Scope too long",
+                )
+                .with_source_code(commit.raw.clone()),
+            );
         }
 
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_commit;
+
+    use super::*;
+
+    #[test]
+    fn test_max_length() {
+        let rule = ScopeMaxLengthRule {
+            opts: LengthOpts(Severity::Error, 4),
+        };
+
+        let commit = parse_commit("feat(api,core): add feature").unwrap();
+        assert!(rule.run(&commit).is_none());
+
+        let commit = parse_commit("feat(api,users): add feature").unwrap();
+        let report = rule.run(&commit).unwrap();
+        let labels: Vec<_> = report.labels().unwrap().collect();
+        assert_eq!(labels.len(), 1);
+        assert_eq!(labels[0].label(), Some("5 characters"));
+    }
+
+    #[test]
+    fn test_non_ascii_length() {
+        let rule = ScopeMaxLengthRule {
+            opts: LengthOpts(Severity::Error, 4),
+        };
+
+        // 4 characters, but 12 bytes
+        let commit = parse_commit("feat(モバイル): add feature").unwrap();
+        assert!(rule.run(&commit).is_none());
+
+        // `e` with a combining accent is one character
+        let commit = parse_commit("feat(cafe\u{301}): add feature").unwrap();
+        assert!(rule.run(&commit).is_none());
+    }
+
+    #[test]
+    fn test_severity_off() {
+        let rule = ScopeMaxLengthRule {
+            opts: LengthOpts(Severity::Off, 1),
+        };
+
+        let commit = parse_commit("feat(api): add feature").unwrap();
+        assert!(rule.run(&commit).is_none());
+    }
+}
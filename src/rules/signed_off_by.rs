@@ -0,0 +1,218 @@
+use crate::parser::{Commit, CommitSpan};
+
+use super::{rule_url, Condition, LintContext, Rule, RuleMeta, Severity, ValueOpts};
+use miette::{miette, LabeledSpan, Report};
+
+pub const META: RuleMeta = RuleMeta {
+    name: "signed-off-by",
+    description: "Last line of the message must (`always`) or must not (`never`) start with the given text. With `always`, its identity must match the author (given with `--author` or read from git when linting a range).",
+    options: "[severity, condition, text]",
+    default: r#"["off", "always", "Signed-off-by:"]"#,
+    good: &["feat: add feature\n\nSigned-off-by: Jane Doe <jane@example.com>"],
+    bad: &["feat: add feature\n\nSigned-off-by: Jane Doe <jane@example.com>\n\nsome footer"],
+};
+
+/// Last line of the message, comments (lines starting with `#`) and empty lines are ignored
+fn last_line(raw: &str) -> CommitSpan<'_> {
+    let mut start = 0;
+    let mut last = CommitSpan::new("", 0, 0);
+    for line in raw.split('\n') {
+        let trimmed = line.trim_end();
+        if !trimmed.is_empty() && !trimmed.starts_with('#') {
+            last = CommitSpan::new(trimmed, start, start + trimmed.len());
+        }
+        start += line.len() + 1;
+    }
+
+    last
+}
+
+pub struct SignedOffByRule {
+    pub opts: ValueOpts,
+}
+
+impl SignedOffByRule {
+    fn report(
+        &self,
+        commit: &Commit,
+        label: LabeledSpan,
+        help: String,
+        message: &str,
+    ) -> Option<Report> {
+        Some(
+            miette!(
+                severity = match self.opts.0 {
+                    Severity::Warning => miette::Severity::Warning,
+                    Severity::Error => miette::Severity::Error,
+                    Severity::Off => miette::Severity::Advice,
+                },
+                labels = vec![label],
+                help = help,
+                code = "rule/signed-off-by",
+                url = rule_url(META.name),
+                "{}",
+                message,
+            )
+            .with_source_code(commit.raw.clone()),
+        )
+    }
+}
+
+impl Rule for SignedOffByRule {
+    fn name(&self) -> &'static str {
+        META.name
+    }
+
+    fn severity(&self) -> &Severity {
+        &self.opts.0
+    }
+
+    fn run(&self, commit: &Commit) -> Option<Report> {
+        self.run_with_context(commit, &LintContext::default())
+    }
+
+    fn run_with_context(&self, commit: &Commit, context: &LintContext) -> Option<Report> {
+        let severity = &self.opts.0;
+        let condition = &self.opts.1;
+        let sign_off = &self.opts.2;
+
+        if severity == &Severity::Off {
+            return None;
+        }
+
+        let line = last_line(&commit.raw);
+        let text = line.to_string();
+        let is_signed_off = text.starts_with(sign_off.as_str());
+
+        match condition {
+            Condition::Never if is_signed_off => self.report(
+                commit,
+                LabeledSpan::at(line.start()..line.end(), "not allowed"),
+                String::from("last line of the message must not start with `")
+                    + sign_off.as_str()
+                    + "`",
+                "Message is signed off",
+            ),
+            Condition::Always if !is_signed_off => self.report(
+                commit,
+                LabeledSpan::at_offset(line.end(), "missing sign-off"),
+                String::from("last line of the message must start with `")
+                    + sign_off.as_str()
+                    + "`, e.g. use `git commit --signoff`",
+                "Message is not signed off",
+            ),
+            Condition::Always => {
+                let author = context.author.as_deref()?.trim();
+                let identity = text[sign_off.len()..].trim();
+                if identity == author {
+                    return None;
+                }
+
+                let start = line.end() - text[sign_off.len()..].trim_start().len();
+                self.report(
+                    commit,
+                    LabeledSpan::at(start..line.end(), "different identity"),
+                    format!("sign off as the author `{}`", author),
+                    "Sign-off doesn't match the author",
+                )
+            }
+            Condition::Never => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_commit;
+
+    use super::*;
+
+    fn rule(condition: Condition) -> SignedOffByRule {
+        SignedOffByRule {
+            opts: ValueOpts(Severity::Error, condition, "Signed-off-by:".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_always_condition() {
+        let rule = rule(Condition::Always);
+
+        let commit = parse_commit(
+            "feat: add feature\n\nsome body\n\nSigned-off-by: Jane Doe <jane@example.com>\n",
+        )
+        .unwrap();
+        assert!(rule.run(&commit).is_none());
+
+        let commit = parse_commit("feat: add feature\n\nsome body").unwrap();
+        assert!(rule.run(&commit).is_some());
+
+        // the sign-off has to be the last line
+        let commit = parse_commit(
+            "feat: add feature\n\nSigned-off-by: Jane Doe <jane@example.com>\nReviewed-by: John Doe",
+        )
+        .unwrap();
+        assert!(rule.run(&commit).is_some());
+    }
+
+    #[test]
+    fn test_comments_are_ignored() {
+        let rule = rule(Condition::Always);
+
+        let commit = parse_commit(
+            "feat: add feature\n\nSigned-off-by: Jane Doe <jane@example.com>\n# Please enter the commit message",
+        )
+        .unwrap();
+        assert!(rule.run(&commit).is_none());
+    }
+
+    #[test]
+    fn test_never_condition() {
+        let rule = rule(Condition::Never);
+
+        let commit = parse_commit("feat: add feature").unwrap();
+        assert!(rule.run(&commit).is_none());
+
+        let commit =
+            parse_commit("feat: add feature\n\nSigned-off-by: Jane Doe <jane@example.com>")
+                .unwrap();
+        assert!(rule.run(&commit).is_some());
+    }
+
+    #[test]
+    fn test_author() {
+        let rule = rule(Condition::Always);
+        let commit =
+            parse_commit("feat: add feature\n\nSigned-off-by: Jane Doe <jane@example.com>")
+                .unwrap();
+
+        let context = LintContext {
+            author: Some("Jane Doe <jane@example.com>".to_string()),
+            ..Default::default()
+        };
+        assert!(rule.run_with_context(&commit, &context).is_none());
+
+        let context = LintContext {
+            author: Some("John Doe <john@example.com>".to_string()),
+            ..Default::default()
+        };
+        let report = rule.run_with_context(&commit, &context).unwrap();
+        assert_eq!(report.to_string(), "Sign-off doesn't match the author");
+        let labels: Vec<_> = report.labels().unwrap().collect();
+        assert_eq!(labels[0].offset(), 34);
+        assert_eq!(labels[0].len(), 27);
+    }
+
+    #[test]
+    fn test_severity_off() {
+        let rule = SignedOffByRule {
+            opts: ValueOpts(
+                Severity::Off,
+                Condition::Always,
+                "Signed-off-by:".to_string(),
+            ),
+        };
+
+        let commit = parse_commit("feat: add feature").unwrap();
+        assert!(rule.run(&commit).is_none());
+    }
+}
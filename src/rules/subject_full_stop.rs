@@ -0,0 +1,151 @@
+use crate::parser::Commit;
+
+use super::{rule_url, Condition, Fix, Rule, RuleMeta, Severity, ValueOpts};
+use miette::{miette, LabeledSpan, Report};
+
+pub const META: RuleMeta = RuleMeta {
+    name: "subject-full-stop",
+    description: "Subject must (`always`) or must not (`never`) end with the given character",
+    options: "[severity, condition, character]",
+    default: r#"["off", "never", "."]"#,
+    good: &["feat: add feature"],
+    bad: &["feat: add feature."],
+};
+
+pub struct SubjectFullStopRule {
+    pub opts: ValueOpts,
+}
+
+impl Rule for SubjectFullStopRule {
+    fn name(&self) -> &'static str {
+        META.name
+    }
+
+    fn severity(&self) -> &Severity {
+        &self.opts.0
+    }
+
+    fn run(&self, commit: &Commit) -> Option<Report> {
+        let severity = &self.opts.0;
+        let condition = &self.opts.1;
+        let full_stop = &self.opts.2;
+
+        if severity == &Severity::Off {
+            return None;
+        }
+
+        let subject = commit.subject.to_string();
+        let ends_with_full_stop = subject.trim_end().ends_with(full_stop.as_str());
+        let is_valid = match condition {
+            Condition::Never => !ends_with_full_stop,
+            Condition::Always => ends_with_full_stop,
+        };
+
+        if !is_valid {
+            let end = commit.subject.start() + subject.trim_end().len();
+            return Some(
+                miette!(
+                    severity = match severity {
+                        Severity::Warning => miette::Severity::Warning,
+                        Severity::Error => miette::Severity::Error,
+                        Severity::Off => miette::Severity::Advice,
+                    },
+                    labels = vec![match condition {
+                        Condition::Never => {
+                            LabeledSpan::at(end - full_stop.len()..end, "not allowed full stop")
+                        }
+                        Condition::Always => LabeledSpan::at_offset(end, "missing full stop"),
+                    }],
+                    help = String::from("subject must")
+                        + match condition {
+                            Condition::Never => " not",
+                            Condition::Always => "",
+                        }
+                        + " end with \""
+                        + full_stop.as_str()
+                        + "\"",
+                    code = "rule/subject-full-stop",
+                    url = rule_url(META.name),
+                    "{}",
+                    match condition {
+                        Condition::Never => "Subject ends with a full stop",
+                        Condition::Always => "Subject is missing a full stop",
+                    },
+                )
+                .with_source_code(commit.raw.clone()),
+            );
+        }
+
+        None
+    }
+
+    fn fix(&self, commit: &Commit) -> Option<Vec<Fix>> {
+        let full_stop = &self.opts.2;
+        let end = commit.subject.start() + commit.subject.to_string().trim_end().len();
+
+        Some(vec![match self.opts.1 {
+            Condition::Never => Fix::delete(end - full_stop.len(), end),
+            Condition::Always => Fix::insert(end, full_stop.as_str()),
+        }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::CommitSpan;
+
+    use super::*;
+
+    #[test]
+    fn test_never_condition() {
+        let rule = SubjectFullStopRule {
+            opts: ValueOpts(Severity::Error, Condition::Never, ".".to_string()),
+        };
+
+        let commit = Commit {
+            subject: CommitSpan::new("add feature", 6, 17),
+            ..Default::default()
+        };
+        assert!(rule.run(&commit).is_none());
+
+        let commit = Commit {
+            subject: CommitSpan::new("add feature.", 6, 18),
+            ..Default::default()
+        };
+        assert!(rule.run(&commit).is_some());
+        assert_eq!(rule.fix(&commit), Some(vec![Fix::delete(17, 18)]));
+    }
+
+    #[test]
+    fn test_always_condition() {
+        let rule = SubjectFullStopRule {
+            opts: ValueOpts(Severity::Error, Condition::Always, ".".to_string()),
+        };
+
+        let commit = Commit {
+            subject: CommitSpan::new("add feature.", 6, 18),
+            ..Default::default()
+        };
+        assert!(rule.run(&commit).is_none());
+
+        let commit = Commit {
+            subject: CommitSpan::new("add feature", 6, 17),
+            ..Default::default()
+        };
+        assert!(rule.run(&commit).is_some());
+        assert_eq!(rule.fix(&commit), Some(vec![Fix::insert(17, ".")]));
+    }
+
+    #[test]
+    fn test_severity_off() {
+        let rule = SubjectFullStopRule {
+            opts: ValueOpts(Severity::Off, Condition::Never, ".".to_string()),
+        };
+
+        let commit = Commit {
+            subject: CommitSpan::new("add feature.", 6, 18),
+            ..Default::default()
+        };
+        assert!(rule.run(&commit).is_none());
+    }
+}
@@ -0,0 +1,110 @@
+use crate::parser::Commit;
+
+use super::{check_pattern, PatternOpts, Rule, RuleMeta, Severity};
+use miette::Report;
+
+pub const META: RuleMeta = RuleMeta {
+    name: "subject-pattern",
+    description: "Subject must (`always`) or must not (`never`) match the given regex",
+    options: "[severity, condition, regex]",
+    default: r#"["off", "always", "[A-Z]+-[0-9]+"]"#,
+    good: &["feat: add feature PROJ-123"],
+    bad: &["feat: add feature"],
+};
+
+pub struct SubjectPatternRule {
+    pub opts: PatternOpts,
+}
+
+impl Rule for SubjectPatternRule {
+    fn name(&self) -> &'static str {
+        META.name
+    }
+
+    fn severity(&self) -> &Severity {
+        &self.opts.0
+    }
+
+    fn run(&self, commit: &Commit) -> Option<Report> {
+        check_pattern(
+            META.name,
+            "subject",
+            Some(&commit.subject),
+            &self.opts,
+            commit,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use regex::Regex;
+
+    use crate::{parser::CommitSpan, rules::Condition};
+
+    use super::*;
+
+    fn rule(condition: Condition) -> SubjectPatternRule {
+        SubjectPatternRule {
+            opts: PatternOpts(
+                Severity::Error,
+                condition,
+                Regex::new("[A-Z]+-[0-9]+").unwrap(),
+            ),
+        }
+    }
+
+    #[test]
+    fn test_always_condition() {
+        let commit = Commit {
+            subject: CommitSpan::new("add feature PROJ-123", 6, 26),
+            ..Default::default()
+        };
+        assert!(rule(Condition::Always).run(&commit).is_none());
+
+        let commit = Commit {
+            subject: CommitSpan::new("add feature", 6, 17),
+            ..Default::default()
+        };
+        let report = rule(Condition::Always).run(&commit).unwrap();
+        let labels: Vec<_> = report.labels().unwrap().collect();
+        assert_eq!((labels[0].offset(), labels[0].len()), (6, 11));
+    }
+
+    #[test]
+    fn test_never_condition() {
+        let commit = Commit {
+            subject: CommitSpan::new("add feature", 6, 17),
+            ..Default::default()
+        };
+        assert!(rule(Condition::Never).run(&commit).is_none());
+
+        // every match is labeled
+        let commit = Commit {
+            subject: CommitSpan::new("fix PROJ-1 and PROJ-2", 6, 27),
+            ..Default::default()
+        };
+        let report = rule(Condition::Never).run(&commit).unwrap();
+        let labels: Vec<_> = report.labels().unwrap().collect();
+        assert_eq!(labels.len(), 2);
+        assert_eq!((labels[0].offset(), labels[0].len()), (10, 6));
+        assert_eq!((labels[1].offset(), labels[1].len()), (21, 6));
+    }
+
+    #[test]
+    fn test_severity_off() {
+        let rule = SubjectPatternRule {
+            opts: PatternOpts(
+                Severity::Off,
+                Condition::Always,
+                Regex::new("[A-Z]+-[0-9]+").unwrap(),
+            ),
+        };
+        let commit = Commit {
+            subject: CommitSpan::new("add feature", 6, 17),
+            ..Default::default()
+        };
+
+        assert!(rule.run(&commit).is_none());
+    }
+}
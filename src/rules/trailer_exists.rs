@@ -0,0 +1,150 @@
+use crate::parser::Commit;
+
+use super::{rule_url, Condition, Rule, RuleMeta, Severity, ValueOpts};
+use miette::{miette, LabeledSpan, Report};
+
+pub const META: RuleMeta = RuleMeta {
+    name: "trailer-exists",
+    description: "Message must (`always`) or must not (`never`) have a trailer which starts with the given text",
+    options: "[severity, condition, text]",
+    default: r#"["off", "always", "Signed-off-by:"]"#,
+    good: &["feat: add feature\n\nSigned-off-by: Jane Doe <jane@example.com>"],
+    bad: &["feat: add feature\n\nsome body"],
+};
+
+pub struct TrailerExistsRule {
+    pub opts: ValueOpts,
+}
+
+impl Rule for TrailerExistsRule {
+    fn name(&self) -> &'static str {
+        META.name
+    }
+
+    fn severity(&self) -> &Severity {
+        &self.opts.0
+    }
+
+    fn run(&self, commit: &Commit) -> Option<Report> {
+        let severity = &self.opts.0;
+        let condition = &self.opts.1;
+        let trailer = &self.opts.2;
+
+        if severity == &Severity::Off {
+            return None;
+        }
+
+        let found: Vec<_> = commit
+            .trailers()
+            .into_iter()
+            .filter(|line| line.to_string().starts_with(trailer.as_str()))
+            .collect();
+        let is_valid = match condition {
+            Condition::Never => found.is_empty(),
+            Condition::Always => !found.is_empty(),
+        };
+
+        if !is_valid {
+            return Some(
+                miette!(
+                    severity = match severity {
+                        Severity::Warning => miette::Severity::Warning,
+                        Severity::Error => miette::Severity::Error,
+                        Severity::Off => miette::Severity::Advice,
+                    },
+                    labels = match condition {
+                        Condition::Never => found
+                            .iter()
+                            .map(|line| LabeledSpan::at(line.start()..line.end(), "not allowed"))
+                            .collect(),
+                        Condition::Always => vec![LabeledSpan::at_offset(
+                            commit.raw.trim_end().len(),
+                            "missing trailer"
+                        )],
+                    },
+                    help = String::from("message must")
+                        + match condition {
+                            Condition::Never => " not",
+                            Condition::Always => "",
+                        }
+                        + " have a `"
+                        + trailer.as_str()
+                        + "` trailer in its last paragraph",
+                    code = "rule/trailer-exists",
+                    url = rule_url(META.name),
+                    "{}",
+                    match condition {
+                        Condition::Never => "Message has a forbidden trailer",
+                        Condition::Always => "Message is missing a trailer",
+                    },
+                )
+                .with_source_code(commit.raw.clone()),
+            );
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_commit;
+
+    use super::*;
+
+    fn rule(condition: Condition) -> TrailerExistsRule {
+        TrailerExistsRule {
+            opts: ValueOpts(Severity::Error, condition, "Signed-off-by:".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_always_condition() {
+        let rule = rule(Condition::Always);
+
+        let commit = parse_commit(
+            "feat: add feature\n\nsome body\n\nSigned-off-by: Jane Doe <jane@example.com>\nReviewed-by: John Doe",
+        )
+        .unwrap();
+        assert!(rule.run(&commit).is_none());
+
+        let commit = parse_commit("feat: add feature\n\nsome body").unwrap();
+        assert!(rule.run(&commit).is_some());
+
+        // the trailer has to be in the last paragraph
+        let commit = parse_commit(
+            "feat: add feature\n\nSigned-off-by: Jane Doe <jane@example.com>\n\nsome footer",
+        )
+        .unwrap();
+        assert!(rule.run(&commit).is_some());
+    }
+
+    #[test]
+    fn test_never_condition() {
+        let rule = rule(Condition::Never);
+
+        let commit = parse_commit("feat: add feature").unwrap();
+        assert!(rule.run(&commit).is_none());
+
+        let commit =
+            parse_commit("feat: add feature\n\nSigned-off-by: Jane Doe <jane@example.com>")
+                .unwrap();
+        let report = rule.run(&commit).unwrap();
+        let labels: Vec<_> = report.labels().unwrap().collect();
+        assert_eq!(labels[0].offset(), 19);
+    }
+
+    #[test]
+    fn test_severity_off() {
+        let rule = TrailerExistsRule {
+            opts: ValueOpts(
+                Severity::Off,
+                Condition::Always,
+                "Signed-off-by:".to_string(),
+            ),
+        };
+
+        let commit = parse_commit("feat: add feature").unwrap();
+        assert!(rule.run(&commit).is_none());
+    }
+}
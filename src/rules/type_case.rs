@@ -0,0 +1,147 @@
+use crate::parser::Commit;
+
+use super::{rule_url, CaseOpts, Condition, Fix, Rule, RuleMeta, Severity};
+use miette::{miette, LabeledSpan, Report};
+
+pub const META: RuleMeta = RuleMeta {
+    name: "type-case",
+    description: "Type must (`always`) or must not (`never`) be in the given case",
+    options: "[severity, condition, \"lower-case\" | \"upper-case\" | \"pascal-case\" | \"camel-case\" | \"kebab-case\" | \"snake-case\" | \"start-case\" | \"sentence-case\"]",
+    default: r#"["off", "always", "lower-case"]"#,
+    good: &["feat: add feature"],
+    bad: &["FEAT: add feature"],
+};
+
+pub struct TypeCaseRule {
+    pub opts: CaseOpts,
+}
+
+impl TypeCaseRule {
+    fn is_valid(&self, commit: &Commit) -> bool {
+        let is_in_case = self.opts.2.matches(&commit.commit_type.to_string());
+
+        match self.opts.1 {
+            Condition::Never => !is_in_case,
+            Condition::Always => is_in_case,
+        }
+    }
+}
+
+impl Rule for TypeCaseRule {
+    fn name(&self) -> &'static str {
+        META.name
+    }
+
+    fn severity(&self) -> &Severity {
+        &self.opts.0
+    }
+
+    fn run(&self, commit: &Commit) -> Option<Report> {
+        let severity = &self.opts.0;
+        let condition = &self.opts.1;
+        let case = &self.opts.2;
+
+        if severity == &Severity::Off || self.is_valid(commit) {
+            return None;
+        }
+
+        Some(
+            miette!(
+                severity = match severity {
+                    Severity::Warning => miette::Severity::Warning,
+                    Severity::Error => miette::Severity::Error,
+                    Severity::Off => miette::Severity::Advice,
+                },
+                labels = vec![LabeledSpan::at(
+                    commit.commit_type.start()..commit.commit_type.end(),
+                    "wrong case"
+                )],
+                help = String::from("type must")
+                    + match condition {
+                        Condition::Never => " not",
+                        Condition::Always => "",
+                    }
+                    + " be in "
+                    + case.to_string().as_str(),
+                code = "rule/type-case",
+                url = rule_url(META.name),
+                "Type has the wrong case",
+            )
+            .with_source_code(commit.raw.clone()),
+        )
+    }
+
+    fn fix(&self, commit: &Commit) -> Option<Vec<Fix>> {
+        // there is no single case to convert to, if the type must only "never" be in a case
+        match self.opts.1 {
+            Condition::Never => None,
+            Condition::Always => Some(vec![Fix::replace(
+                &commit.commit_type,
+                self.opts.2.convert(&commit.commit_type.to_string()),
+            )]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::CommitSpan, rules::TargetCase};
+
+    use super::*;
+
+    #[test]
+    fn test_always_condition() {
+        let rule = TypeCaseRule {
+            opts: CaseOpts(Severity::Error, Condition::Always, TargetCase::Lower),
+        };
+
+        let commit = Commit {
+            commit_type: CommitSpan::new("feat", 0, 4),
+            ..Default::default()
+        };
+        assert!(rule.run(&commit).is_none());
+
+        let commit = Commit {
+            commit_type: CommitSpan::new("FEAT", 0, 4),
+            ..Default::default()
+        };
+        assert!(rule.run(&commit).is_some());
+        assert_eq!(
+            rule.fix(&commit),
+            Some(vec![Fix::replace(&commit.commit_type, "feat")])
+        );
+    }
+
+    #[test]
+    fn test_never_condition() {
+        let rule = TypeCaseRule {
+            opts: CaseOpts(Severity::Error, Condition::Never, TargetCase::Upper),
+        };
+
+        let commit = Commit {
+            commit_type: CommitSpan::new("FEAT", 0, 4),
+            ..Default::default()
+        };
+        assert!(rule.run(&commit).is_some());
+        assert!(rule.fix(&commit).is_none());
+
+        let commit = Commit {
+            commit_type: CommitSpan::new("feat", 0, 4),
+            ..Default::default()
+        };
+        assert!(rule.run(&commit).is_none());
+    }
+
+    #[test]
+    fn test_severity_off() {
+        let rule = TypeCaseRule {
+            opts: CaseOpts(Severity::Off, Condition::Always, TargetCase::Lower),
+        };
+
+        let commit = Commit {
+            commit_type: CommitSpan::new("FEAT", 0, 4),
+            ..Default::default()
+        };
+        assert!(rule.run(&commit).is_none());
+    }
+}
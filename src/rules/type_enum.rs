@@ -0,0 +1,135 @@
+use crate::parser::Commit;
+
+use super::{rule_url, Condition, EnumOpts, Rule, RuleMeta, Severity};
+use miette::{miette, LabeledSpan, Report};
+
+pub const META: RuleMeta = RuleMeta {
+    name: "type-enum",
+    description: "Type must be one of (`always`) or must not be one of (`never`) the given types. The examples allow the types of the `conventional` preset of `init`.",
+    options: "[severity, condition, [types]]",
+    default: r#"["off", "always", []]"#,
+    good: &["feat: add feature", "fix: fix bug"],
+    bad: &["feature: add feature"],
+};
+
+pub struct TypeEnumRule {
+    pub opts: EnumOpts,
+}
+
+impl Rule for TypeEnumRule {
+    fn name(&self) -> &'static str {
+        META.name
+    }
+
+    fn severity(&self) -> &Severity {
+        &self.opts.0
+    }
+
+    fn run(&self, commit: &Commit) -> Option<Report> {
+        let severity = &self.opts.0;
+        let condition = &self.opts.1;
+        let types = &self.opts.2;
+
+        if severity == &Severity::Off || types.is_empty() {
+            return None;
+        }
+
+        let commit_type = &commit.commit_type;
+        let is_in_types = types.contains(&commit_type.to_string());
+        let is_valid = match condition {
+            Condition::Never => !is_in_types,
+            Condition::Always => is_in_types,
+        };
+        if !is_valid {
+            return Some(
+                miette!(
+                    severity = match severity {
+                        Severity::Warning => miette::Severity::Warning,
+                        Severity::Error => miette::Severity::Error,
+                        Severity::Off => miette::Severity::Advice,
+                    },
+                    labels = vec![LabeledSpan::at(
+                        commit_type.start()..commit_type.end(),
+                        "not allowed type"
+                    )],
+                    help = String::from("type must")
+                        + match condition {
+                            Condition::Never => " not",
+                            Condition::Always => "",
+                        }
+                        + " be one of "
+                        + types.join(", ").as_str(),
+                    code = "rule/type-enum",
+                    url = rule_url(META.name),
+                    "Type not allowed",
+                )
+                .with_source_code(commit.raw.clone()),
+            );
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::CommitSpan;
+
+    use super::*;
+
+    #[test]
+    fn test_never_condition() {
+        let mut commit: Commit<'_> = Default::default();
+
+        let rule = TypeEnumRule {
+            opts: EnumOpts(
+                Severity::Error,
+                Condition::Never,
+                vec!["wip".to_string(), "tmp".to_string()],
+            ),
+        };
+
+        commit.commit_type = CommitSpan::new("feat", 0, 4);
+        assert!(rule.run(&commit).is_none());
+
+        commit.commit_type = CommitSpan::new("wip", 0, 3);
+        assert!(rule.run(&commit).is_some());
+    }
+
+    #[test]
+    fn test_always_condition() {
+        let mut commit: Commit<'_> = Default::default();
+
+        let rule = TypeEnumRule {
+            opts: EnumOpts(
+                Severity::Error,
+                Condition::Always,
+                vec!["feat".to_string(), "fix".to_string()],
+            ),
+        };
+
+        commit.commit_type = CommitSpan::new("feat", 0, 4);
+        assert!(rule.run(&commit).is_none());
+
+        commit.commit_type = CommitSpan::new("feature", 0, 7);
+        assert!(rule.run(&commit).is_some());
+    }
+
+    #[test]
+    fn test_severity_off_and_empty_list() {
+        let commit = Commit {
+            commit_type: CommitSpan::new("feature", 0, 7),
+            ..Default::default()
+        };
+
+        let rule = TypeEnumRule {
+            opts: EnumOpts(Severity::Off, Condition::Always, vec!["feat".to_string()]),
+        };
+        assert!(rule.run(&commit).is_none());
+
+        let rule = TypeEnumRule {
+            opts: EnumOpts(Severity::Error, Condition::Always, vec![]),
+        };
+        assert!(rule.run(&commit).is_none());
+    }
+}